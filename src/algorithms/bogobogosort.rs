@@ -0,0 +1,122 @@
+use rand::prelude::*;
+use super::recorder::{ Recorder, less, recurse, swap };
+
+comparison_sort! {
+    /// A trait providing the bogobogosort method.
+    pub trait Bogobogosort: bogobogosort, "bogobogosort";
+    tail " -- or dies trying", " rather than letting it die trying";
+    core |arr, is_less, steps| bogobogosort_aux(arr, &mut rand::thread_rng(), is_less, steps);
+
+    fn bogobogosort_with_rng<G>(rng: &mut G): " with a given random number generator"
+        where { T: PartialEq + PartialOrd, G: Rng, }
+        steps {
+            <G> (rng: G) where {
+                T: PartialEq + PartialOrd + Clone + Send + 'static,
+                G: Rng + Send + 'static,
+            } => (&mut rng)
+        }
+        => |arr, steps| bogobogosort_aux(arr, rng, &mut T::lt, steps);
+
+    fn bogobogosort_with_seed(seed: u64): " with a given seed"
+        where { T: PartialEq + PartialOrd, }
+        => |arr, steps| bogobogosort_aux(arr, &mut StdRng::seed_from_u64(seed), &mut T::lt, steps);
+}
+
+/// Returns the expected number of shuffles bogobogosort makes on a uniformly random permutation of
+/// `n` distinct elements, counting the ones of every copy.
+///
+/// Sorting a copy of `m` elements takes `m * E(m - 1) + m - 1` shuffles of it and its prefixes, as
+/// its last element belongs after the others with a probability of `1 / m`, and the sorted copy then
+/// matches the original with a probability of `1 / m!`, so the expected number is
+/// `m! * (m * E(m - 1) + m - 1) + m! - 1`. It grows about like the product of the factorials of 1 up
+/// to `n`, from 71 shuffles for 3 elements to 6911 for 4 and more than four million for 5.
+pub fn bogobogosort_expected_iterations(n: usize) -> f64 {
+    let mut expected = 0.0;
+    let mut factorial = 1.0;
+    for m in 2..=n {
+        factorial *= m as f64;
+        expected = factorial * (m as f64 * expected + (m - 1) as f64) + factorial - 1.0;
+    }
+
+    return expected;
+}
+
+/// Auxiliary function bogobogosorting the array: bogobogosorting a copy of it, and shuffling the
+/// array and starting over unless it is in the same order as the sorted copy.
+fn bogobogosort_aux<T, F, G, R>(arr: &mut [T], rng: &mut G, is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          G: Rng,
+          R: Recorder<T>,
+{
+    if arr.len() <= 1 {
+        return;
+    }
+
+    loop {
+        // The copies hold the indices of the elements rather than the elements themselves, which
+        // need not be `Clone`, and are shuffled without moving anything in the array.
+        let mut copy: Vec<usize> = (0..arr.len()).collect();
+        steps.record_allocation();
+        sort_copy(arr, &mut copy, rng, is_less, steps);
+        if steps.is_stopped() || copy.iter().enumerate().all(|(i, &j)| is_equal(arr, i, j, is_less, steps)) {
+            return;
+        }
+
+        // A Fisher-Yates shuffle, like `SliceRandom::shuffle`, but recording the swaps.
+        for i in (1..arr.len()).rev() {
+            swap(arr, i, rng.gen_range(0..=i), steps);
+        }
+    }
+}
+
+/// Auxiliary function bogobogosorting the indices in `list` by the elements they point to:
+/// bogobogosorting a copy of them, and shuffling them and starting over unless they are in the same
+/// order as the sorted copy.
+fn bogobogosort_rec<T, F, G, R>(arr: &[T], list: &mut [usize], rng: &mut G, is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          G: Rng,
+          R: Recorder<T>,
+{
+    if list.len() <= 1 {
+        return;
+    }
+
+    loop {
+        let mut copy = list.to_vec();
+        steps.record_allocation();
+        sort_copy(arr, &mut copy, rng, is_less, steps);
+        if steps.is_stopped() || list.iter().zip(&copy).all(|(&i, &j)| is_equal(arr, i, j, is_less, steps)) {
+            return;
+        }
+
+        list.shuffle(rng);
+    }
+}
+
+/// Auxiliary function sorting a copy of at least two indices: bogobogosorting all of them but the
+/// last, and shuffling the copy and starting over unless the last one belongs after the others.
+fn sort_copy<T, F, G, R>(arr: &[T], copy: &mut [usize], rng: &mut G, is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          G: Rng,
+          R: Recorder<T>,
+{
+    let m = copy.len();
+
+    loop {
+        recurse(steps, |steps| bogobogosort_rec(arr, &mut copy[..m-1], rng, is_less, steps));
+        if steps.is_stopped() || !less(arr, copy[m-1], copy[m-2], is_less, steps) {
+            return;
+        }
+
+        copy.shuffle(rng);
+    }
+}
+
+/// Auxiliary function checking whether neither of the elements at `i` and `j` is less than the
+/// other.
+fn is_equal<T, F, R>(arr: &[T], i: usize, j: usize, is_less: &mut F, steps: &mut R) -> bool
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    return i == j || (!less(arr, i, j, is_less, steps) && !less(arr, j, i, is_less, steps));
+}
@@ -1,177 +1,50 @@
-use std::time::{ Instant, Duration };
 use rand::prelude::*;
-use super::IsSorted;
-
-/// A trait providing the bogosort method.
-pub trait Bogosort<T: PartialEq + PartialOrd + Clone + Copy> {
-    /// The bogosort algorithm.
-    ///
-    /// Sorts the `Vec` it is called on -- or dies trying.
-    fn bogosort(&mut self);
-
-    /// The bogosort algorithm but timed.
-    ///
-    /// Sorts the `Vec` it is called on and returns the `Duration` of the process -- or dies trying.
-    fn bogosort_timed(&mut self) -> Duration;
-
-    /// The bogosort algorithm but stepped.
-    ///
-    /// Sorts the `Vec` it is called on and returns a `Vec` containing each step of the process 
-    /// -- or dies trying.
-    fn bogosort_stepped(&mut self) -> Vec<Vec<T>>;
-
-    /// The bogosort algorithm but stepped _and_ timed.
-    ///
-    /// Sorts the `Vec` it is called on and returns a `Vec` containing each step of the process,
-    /// including the `Duration` of the entire process -- or dies trying.
-    fn bogosort_stepped_and_timed(&mut self) -> (Vec<Vec<T>>, Duration);
+use super::recorder::{ Recorder, less };
+use super::trace::SortEvent;
+
+comparison_sort! {
+    /// A trait providing the bogosort method.
+    pub trait Bogosort: bogosort, "bogosort";
+    tail " -- or dies trying", " rather than letting it die trying";
+    core |arr, is_less, steps| bogosort_aux(arr, &mut rand::thread_rng(), is_less, steps);
+
+    fn bogosort_with_rng<G>(rng: &mut G): " with a given random number generator"
+        where { T: PartialEq + PartialOrd, G: Rng, }
+        steps {
+            <G> (rng: G) where {
+                T: PartialEq + PartialOrd + Clone + Send + 'static,
+                G: Rng + Send + 'static,
+            } => (&mut rng)
+        }
+        => |arr, steps| bogosort_aux(arr, rng, &mut T::lt, steps);
+
+    fn bogosort_with_seed(seed: u64): " with a given seed"
+        where { T: PartialEq + PartialOrd, }
+        => |arr, steps| bogosort_aux(arr, &mut StdRng::seed_from_u64(seed), &mut T::lt, steps);
 }
 
-/// The trait implementation of the bogosort algorithm.
-impl<T> Bogosort<T> for Vec<T> 
-    where T: PartialEq + PartialOrd + Clone + Copy,
-{
-    fn bogosort(&mut self) {
-        if self.len() <= 1 {
-            return;
-        }
-
-        let mut rng = rand::thread_rng();
-        while !self.is_sorted() {
-            self.shuffle(&mut rng);
-        }
-    }
-
-    fn bogosort_timed(&mut self) -> Duration {
-        let time = Instant::now();
-
-        if self.len() <= 1 {
-            return time.elapsed();
-        }
-
-        let mut rng = rand::thread_rng();
-        while !self.is_sorted() {
-            self.shuffle(&mut rng);
-        }
-
-        return time.elapsed();
-    }
-
-    fn bogosort_stepped(&mut self) -> Vec<Vec<T>> {
-        let mut steps = vec![self.clone()];
-
-        if self.len() <= 1 {
-            return steps;
-        }
-
-        let mut rng = rand::thread_rng();
-        while !self.is_sorted() {
-            self.shuffle(&mut rng);
-            steps.push(self.clone());
-        }
-
-        return steps;
-    }
-
-    fn bogosort_stepped_and_timed(&mut self) -> (Vec<Vec<T>>, Duration) {
-        let time = Instant::now();
-
-        let mut steps = vec![self.clone()];
-
-        if self.len() <= 1 {
-            return (steps, time.elapsed());
-        }
-
-        let mut rng = rand::thread_rng();
-        while !self.is_sorted() {
-            self.shuffle(&mut rng);
-            steps.push(self.clone());
-        }
-
-        (steps, time.elapsed())
-    }
-}
-
-/// The bogosort algorithm.
-///
-/// Sorts the given `Vec` and returns the result -- or dies trying.
-pub fn bogosort<T>(mut arr: Vec<T>) -> Vec<T> 
-    where T: PartialEq + PartialOrd + Clone + Copy,
-{
-    if arr.len() <= 1 {
-        return arr;
-    }
-
-    let mut rng = rand::thread_rng();
-    while !arr.is_sorted() {
-        arr.shuffle(&mut rng);
-    }
-
-    return arr;
-}
-
-/// The bogosort algorithm but timed.
+/// Returns the expected number of shuffles bogosort makes on a uniformly random permutation of `n`
+/// distinct elements.
 ///
-/// Sorts the given `Vec` and returns the result and the `Duration` of the process -- or dies trying.
-pub fn bogosort_timed<T>(mut arr: Vec<T>) -> (Vec<T>, Duration)
-    where T: PartialEq + PartialOrd + Clone + Copy,
-{
-    let time = Instant::now();
-
-    if arr.len() <= 1 {
-        return (arr, time.elapsed());
-    }
-
-    let mut rng = rand::thread_rng();
-    while !arr.is_sorted() {
-        arr.shuffle(&mut rng);
-    }
-
-    (arr, time.elapsed())
+/// Every shuffle is sorted with a probability of `1 / n!`, so the expected number is `n! - 1`,
+/// which no longer fits in an `f64` for more than 170 elements and is infinite then.
+pub fn bogosort_expected_iterations(n: usize) -> f64 {
+    return (1..=n).map(|k| k as f64).product::<f64>() - 1.0;
 }
 
-/// The bogosort algorithm but stepped.
-///
-/// Sorts the given `Vec` and returns the result and a `Vec` containing the steps of the process --
-/// or dies trying.
-pub fn bogosort_stepped<T>(mut arr: Vec<T>) -> (Vec<T>, Vec<Vec<T>>) 
-    where T: PartialEq + PartialOrd + Clone + Copy,
+/// Auxiliary function shuffling the array with `rng` until it happens to be sorted.
+fn bogosort_aux<T, F, G, R>(arr: &mut [T], rng: &mut G, is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          G: Rng,
+          R: Recorder<T>,
 {
-    let mut steps = vec![arr.clone()];
-
-    if arr.len() <= 1 {
-        return (arr, steps);
-    }
-
-    let mut rng = rand::thread_rng();
-    while !arr.is_sorted() {
-        arr.shuffle(&mut rng);
-        steps.push(arr.clone());
-    }
-
-    (arr, steps)
-}
-
-/// The bogosort algorithm but stepped _and_ timed.
-///
-/// Sorts the given `Vec` and returns the result and a `Vec` containing the steps of the process,
-/// including the `Duration` of the entire process -- or dies trying.
-pub fn bogosort_stepped_and_timed<T>(mut arr: Vec<T>) -> (Vec<T>, Vec<Vec<T>>, Duration)
-    where T: PartialEq + PartialOrd + Clone + Copy,
-{
-    let time = Instant::now();
-
-    let mut steps = vec![arr.clone()];
-
-    if arr.len() <= 1 {
-        return (arr, steps, time.elapsed());
-    }
-
-    let mut rng = rand::thread_rng();
-    while !arr.is_sorted() {
-        arr.shuffle(&mut rng);
-        steps.push(arr.clone());
+    while !steps.is_stopped() && (1..arr.len()).any(|i| less(arr, i, i-1, is_less, steps)) {
+        // A Fisher-Yates shuffle, like `SliceRandom::shuffle`, but recording the swaps.
+        for i in (1..arr.len()).rev() {
+            let j = rng.gen_range(0..=i);
+            arr.swap(i, j);
+            steps.record_event(SortEvent::Swap(i, j));
+        }
+        steps.record(arr);
     }
-
-    (arr, steps, time.elapsed())
 }
@@ -0,0 +1,355 @@
+use super::bogobogosort::bogobogosort_expected_iterations;
+use super::bogosort::bogosort_expected_iterations;
+use super::bozosort::bozosort_expected_iterations;
+use super::permutation_sort::permutation_sort_expected_iterations;
+use super::sorter::Algorithm;
+
+/// Facts about one of the sorting algorithms, as this crate implements it with its default
+/// options.
+///
+/// The complexities are in terms of the number of elements `n` and, for the non-comparison sorts,
+/// the range of the keys `k`, the radix `b` and the number of digits `w` of the keys. Comb sort's
+/// average case also depends on the number of its gaps `p`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlgorithmInfo {
+    /// The algorithm the facts are about.
+    pub algorithm: Algorithm,
+    /// The human readable name of the algorithm, e.g. `Gnome Sort`.
+    pub name: &'static str,
+    /// The time complexity in the best case, e.g. `O(n)`.
+    pub best: &'static str,
+    /// The time complexity in the average case.
+    pub average: &'static str,
+    /// The time complexity in the worst case.
+    pub worst: &'static str,
+    /// The auxiliary space the algorithm needs besides the input, recursion included.
+    pub space: &'static str,
+    /// Whether equal elements keep their order.
+    pub stable: bool,
+    /// Whether the algorithm only moves the elements around within the input, rather than through
+    /// buffers or other data structures the size of the input.
+    pub in_place: bool,
+    /// Whether the algorithm gets faster the more sorted the input already is.
+    pub adaptive: bool,
+    /// Whether the algorithm always takes the same steps for the same input, short of seeding its
+    /// random number generator through its `_with_seed` variants.
+    pub deterministic: bool,
+}
+
+/// The facts about all of the algorithms, in the order of `Algorithm::ALL`.
+const CATALOG: [AlgorithmInfo; 24] = [
+    AlgorithmInfo {
+        algorithm: Algorithm::Bogobogosort,
+        name: "Bogobogosort",
+        best: "O(n^2)",
+        average: "O(n! * 1! * 2! * ... * n!)",
+        worst: "unbounded",
+        space: "O(n^2)",
+        stable: false,
+        in_place: true,
+        adaptive: true,
+        deterministic: false,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Bogosort,
+        name: "Bogosort",
+        best: "O(n)",
+        average: "O(n * n!)",
+        worst: "unbounded",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: true,
+        deterministic: false,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Bozosort,
+        name: "Bozosort",
+        best: "O(n)",
+        average: "O(n!)",
+        worst: "unbounded",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: true,
+        deterministic: false,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::BubbleSort,
+        name: "Bubble Sort",
+        best: "O(n)",
+        average: "O(n^2)",
+        worst: "O(n^2)",
+        space: "O(1)",
+        stable: true,
+        in_place: true,
+        adaptive: true,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::CocktailShakerSort,
+        name: "Cocktail Shaker Sort",
+        best: "O(n)",
+        average: "O(n^2)",
+        worst: "O(n^2)",
+        space: "O(1)",
+        stable: true,
+        in_place: true,
+        adaptive: true,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::CombSort,
+        name: "Comb Sort",
+        best: "O(n log n)",
+        average: "O(n^2 / 2^p)",
+        worst: "O(n^2)",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::CountingSort,
+        name: "Counting Sort",
+        best: "O(n + k)",
+        average: "O(n + k)",
+        worst: "O(n + k)",
+        space: "O(n + k)",
+        stable: true,
+        in_place: false,
+        adaptive: false,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::GnomeSort,
+        name: "Gnome Sort",
+        best: "O(n)",
+        average: "O(n^2)",
+        worst: "O(n^2)",
+        space: "O(1)",
+        stable: true,
+        in_place: true,
+        adaptive: true,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::HeapSort,
+        name: "Heap Sort",
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::InsertionSort,
+        name: "Insertion Sort",
+        best: "O(n)",
+        average: "O(n^2)",
+        worst: "O(n^2)",
+        space: "O(1)",
+        stable: true,
+        in_place: true,
+        adaptive: true,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Introsort,
+        name: "Introsort",
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+        space: "O(log n)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::LsdRadixSort,
+        name: "LSD Radix Sort",
+        best: "O(w (n + b))",
+        average: "O(w (n + b))",
+        worst: "O(w (n + b))",
+        space: "O(n + b)",
+        stable: true,
+        in_place: false,
+        adaptive: false,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::MergeSort,
+        name: "Merge Sort",
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+        space: "O(n)",
+        stable: true,
+        in_place: false,
+        adaptive: false,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::MsdRadixSort,
+        name: "MSD Radix Sort",
+        best: "O(n + b)",
+        average: "O(w (n + b))",
+        worst: "O(w (n + b))",
+        space: "O(n + w b)",
+        stable: true,
+        in_place: false,
+        adaptive: false,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::OddEvenSort,
+        name: "Odd-Even Sort",
+        best: "O(n)",
+        average: "O(n^2)",
+        worst: "O(n^2)",
+        space: "O(1)",
+        stable: true,
+        in_place: true,
+        adaptive: true,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Pdqsort,
+        name: "Pattern-Defeating Quicksort",
+        best: "O(n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+        space: "O(log n)",
+        stable: false,
+        in_place: true,
+        adaptive: true,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::PermutationSort,
+        name: "Permutation Sort",
+        best: "O(n)",
+        average: "O(n!)",
+        worst: "O(n!)",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: true,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Quicksort,
+        name: "Quicksort",
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n^2)",
+        space: "O(log n)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::SelectionSort,
+        name: "Selection Sort",
+        best: "O(n^2)",
+        average: "O(n^2)",
+        worst: "O(n^2)",
+        space: "O(1)",
+        stable: true,
+        in_place: true,
+        adaptive: false,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::ShellSort,
+        name: "Shell Sort",
+        best: "O(n log n)",
+        average: "unknown",
+        worst: "unknown",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: true,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Slowsort,
+        name: "Slowsort",
+        best: "O(n^(log n))",
+        average: "O(n^(log n))",
+        worst: "O(n^(log n))",
+        space: "O(n)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::StoogeSort,
+        name: "Stooge Sort",
+        best: "O(n^2.71)",
+        average: "O(n^2.71)",
+        worst: "O(n^2.71)",
+        space: "O(log n)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Timsort,
+        name: "Timsort",
+        best: "O(n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+        space: "O(n)",
+        stable: true,
+        in_place: false,
+        adaptive: true,
+        deterministic: true,
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::TreeSort,
+        name: "Tree Sort",
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n^2)",
+        space: "O(n)",
+        stable: true,
+        in_place: false,
+        adaptive: false,
+        deterministic: true,
+    },
+];
+
+/// Returns the facts about all of the algorithms, in alphabetical order.
+pub fn catalog() -> &'static [AlgorithmInfo] {
+    return &CATALOG;
+}
+
+impl Algorithm {
+    /// Returns the facts about the algorithm.
+    pub fn info(self) -> &'static AlgorithmInfo {
+        return &CATALOG[self as usize];
+    }
+
+    /// Returns the expected number of iterations of the algorithm on a uniformly random permutation
+    /// of `n` distinct elements, for the algorithms which only ever stumble upon the sorted one:
+    /// the shuffles of bogosort and bogobogosort, the swaps of bozosort and the permutations of
+    /// permutation sort. The others have `None`.
+    pub fn expected_iterations(self, n: usize) -> Option<f64> {
+        return match self {
+            Algorithm::Bogobogosort => Some(bogobogosort_expected_iterations(n)),
+            Algorithm::Bogosort => Some(bogosort_expected_iterations(n)),
+            Algorithm::Bozosort => Some(bozosort_expected_iterations(n)),
+            Algorithm::PermutationSort => Some(permutation_sort_expected_iterations(n)),
+            _ => None,
+        };
+    }
+}
@@ -0,0 +1,47 @@
+use super::recorder::{ Recorder, less, swap };
+use super::trace::SortEvent;
+
+comparison_sort! {
+    /// A trait providing the cocktail shaker sort method.
+    pub trait CocktailShakerSort: cocktail_shaker_sort, "cocktail shaker sort";
+    stepped "A step is recorded for every swap, like in `bubble_sort_stepped`.";
+    core |arr, is_less, steps| cocktail_shaker_sort_aux(arr, is_less, steps);
+}
+
+/// Auxiliary function alternating bubble passes to the right and to the left.
+fn cocktail_shaker_sort_aux<T, F, R>(arr: &mut [T], is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    let mut start = 0;
+    let mut end = arr.len() - 1;
+
+    let mut is_sorted = false;
+    while !is_sorted {
+        is_sorted = true;
+
+        // Bubble the largest element of the unsorted range to its end.
+        for i in start..end {
+            if less(arr, i+1, i, is_less, steps) {
+                is_sorted = false;
+                swap(arr, i, i+1, steps);
+            }
+        }
+
+        if is_sorted {
+            break;
+        }
+        steps.record_event(SortEvent::MarkSorted(end));
+        end -= 1;
+
+        // Bubble the smallest element of the unsorted range to its start.
+        for i in (start..end).rev() {
+            if less(arr, i+1, i, is_less, steps) {
+                is_sorted = false;
+                swap(arr, i, i+1, steps);
+            }
+        }
+        steps.record_event(SortEvent::MarkSorted(start));
+        start += 1;
+    }
+}
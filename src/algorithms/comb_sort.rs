@@ -0,0 +1,59 @@
+use super::recorder::{ Recorder, less, swap };
+
+/// The shrink factor used by comb sort unless another one is given, as suggested by Lacey and Box.
+pub const DEFAULT_SHRINK: f64 = 1.3;
+
+comparison_sort! {
+    /// A trait providing the comb sort method.
+    pub trait CombSort: comb_sort, "comb sort", " using a shrink factor of 1.3";
+    core |arr, is_less, steps| comb_sort_aux(arr, DEFAULT_SHRINK, is_less, steps);
+
+    fn comb_sort_with_shrink(shrink: f64): " with a given shrink factor"
+        where { T: PartialEq + PartialOrd, }
+        panics "Panics if `shrink` is not greater than one."
+        check |_arr| { assert_shrink(shrink); }
+        => |arr, steps| comb_sort_aux(arr, shrink, &mut T::lt, steps);
+}
+
+/// Auxiliary function panicking unless `shrink` is greater than one, which is checked before
+/// anything else so arrays too short to be sorted don't let an invalid factor through.
+fn assert_shrink(shrink: f64) {
+    assert!(shrink > 1.0, "the shrink factor of comb sort must be greater than one, got {}", shrink);
+}
+
+/// Auxiliary function running bubble passes over a gap shrinking by `shrink` each pass.
+fn comb_sort_aux<T, F, R>(arr: &mut [T], shrink: f64, is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    let mut gap = arr.len();
+
+    // Like in bubble sort, every pass with a gap of one moves the largest element of the unsorted
+    // range to its end, which bounds the passes even when the comparisons are inconsistent.
+    let mut end = arr.len();
+
+    let mut is_sorted = false;
+    while !is_sorted {
+        gap = (gap as f64 / shrink) as usize;
+        if gap <= 1 {
+            // A gap of one is a plain bubble sort pass, which
+            // finishes the job once it no longer swaps anything.
+            gap = 1;
+            is_sorted = true;
+        }
+
+        for i in 0..end - gap {
+            if less(arr, i+gap, i, is_less, steps) {
+                swap(arr, i, i+gap, steps);
+
+                if gap == 1 {
+                    is_sorted = false;
+                }
+            }
+        }
+
+        if gap == 1 {
+            end -= 1;
+        }
+    }
+}
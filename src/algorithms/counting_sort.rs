@@ -0,0 +1,72 @@
+use super::merge_sort::merge_sort_aux;
+use super::radix_key::{ RadixItem, write_back };
+use super::recorder::Recorder;
+use super::trace::SortEvent;
+
+/// The range of keys counting sort counts for any array, however short.
+const MIN_RANGE: usize = 1 << 16;
+
+/// The range of keys per value counting sort counts for arrays long enough to exceed `MIN_RANGE`.
+const RANGE_PER_VALUE: usize = 16;
+
+radix_sort! {
+    /// A trait providing the counting sort method.
+    ///
+    /// Not being a comparison sort, it has no variants taking a comparator, only ones taking a key
+    /// extraction function, whose keys it sorts by instead.
+    /// It orders floats like `total_cmp` does anyway, so it has no `_total` variants either.
+    ///
+    /// Counting takes memory proportional to the range of the keys, so when that range is much
+    /// larger than the array, like for most floats or widely spread 64-bit integers, it falls back
+    /// to merge sort rather than running out of memory.
+    pub trait CountingSort: counting_sort, "counting sort";
+    stepped "A step is recorded for every value written back to the array.";
+    core |arr, steps| counting_sort_aux(arr, steps);
+}
+
+/// Auxiliary function counting the occurrences of every key between the smallest and the largest,
+/// or merge sorting the keys if there are too many of those to count.
+fn counting_sort_aux<T, R>(arr: &mut [T], steps: &mut R)
+    where T: RadixItem,
+          R: Recorder<T>,
+{
+    let min = arr.iter().map(RadixItem::key).min().expect("the array is not empty");
+    let max = arr.iter().map(RadixItem::key).max().expect("the array is not empty");
+
+    let max_range = arr.len().saturating_mul(RANGE_PER_VALUE).max(MIN_RANGE);
+    let range = match usize::try_from(max - min).ok().and_then(|range| range.checked_add(1)) {
+        Some(range) if range <= max_range => range,
+        _ => {
+            // Merge sort is stable too, so the result is the same.
+            merge_sort_aux(arr, &mut |a: &T, b: &T| a.key() < b.key(), steps);
+            return;
+        }
+    };
+
+    let mut counts = vec![0usize; range];
+    steps.record_allocation();
+    for (i, item) in arr.iter().enumerate() {
+        steps.record_event(SortEvent::Read(i));
+        counts[(item.key() - min) as usize] += 1;
+    }
+
+    // Turn the counts into the index the first value of every key goes to.
+    let mut total = 0;
+    for count in counts.iter_mut() {
+        let current = *count;
+        *count = total;
+        total += current;
+    }
+
+    // Placing the values in their original order keeps the sort stable.
+    let mut sorted = arr.to_vec();
+    steps.record_allocation();
+    for (i, item) in arr.iter().enumerate() {
+        steps.record_event(SortEvent::Read(i));
+        let key = (item.key() - min) as usize;
+        sorted[counts[key]] = *item;
+        counts[key] += 1;
+    }
+
+    write_back(arr, 0, &sorted, steps);
+}
@@ -0,0 +1,62 @@
+use super::recorder::{ Recorder, less, swap };
+use super::trace::SortEvent;
+
+comparison_sort! {
+    /// A trait providing the heap sort method.
+    pub trait HeapSort: heap_sort, "heap sort";
+    stepped "A step is recorded for every swap, both while building the heap and while extracting \
+        from it.";
+    core |arr, is_less, steps| {
+        let length = arr.len();
+        heap_sort_aux(arr, 0, length, is_less, steps)
+    };
+}
+
+/// Auxiliary function heap sorting `arr[lo..hi]`.
+pub(super) fn heap_sort_aux<T, F, R>(arr: &mut [T], lo: usize, hi: usize, is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    let length = hi - lo;
+
+    // Build a max-heap bottom-up, starting from the last parent.
+    for root in (0..length / 2).rev() {
+        sift_down(arr, lo, root, length, is_less, steps);
+    }
+
+    // Repeatedly move the maximum behind the shrinking heap.
+    for end in (1..length).rev() {
+        swap(arr, lo, lo + end, steps);
+        steps.record_event(SortEvent::MarkSorted(lo + end));
+
+        sift_down(arr, lo, 0, end, is_less, steps);
+    }
+    steps.record_event(SortEvent::MarkSorted(lo));
+}
+
+/// Auxiliary function sifting the element at `root` down the heap stored in
+/// `arr[lo..lo + end]`, where the heap indices are relative to `lo`.
+fn sift_down<T, F, R>(arr: &mut [T], lo: usize, mut root: usize, end: usize, is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    loop {
+        let mut child = 2 * root + 1;
+        if child >= end {
+            return;
+        }
+
+        // Pick the larger of the two children.
+        if child + 1 < end && less(arr, lo + child, lo + child + 1, is_less, steps) {
+            child += 1;
+        }
+
+        if less(arr, lo + root, lo + child, is_less, steps) {
+            swap(arr, lo + root, lo + child, steps);
+
+            root = child;
+        } else {
+            return;
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use super::heap_sort::heap_sort_aux;
+use super::insertion_sort::insertion_sort_range;
+use super::quicksort::{ median_of_three, partition };
+use super::recorder::{ Recorder, recurse };
+use super::trace::SortEvent;
+
+/// The size of the partitions below which introsort switches to insertion sort.
+const INSERTION_THRESHOLD: usize = 16;
+
+comparison_sort! {
+    /// A trait providing the introsort method.
+    pub trait Introsort: introsort, "introsort";
+    stepped "A step is recorded for every swap, including those of the heap sort and insertion sort \
+        it falls back to.";
+    core |arr, is_less, steps| introsort_aux(arr, is_less, steps);
+}
+
+/// Auxiliary function starting the recursion with a depth limit of `2 * log2(n)`.
+fn introsort_aux<T, F, R>(arr: &mut [T], is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    let length = arr.len();
+    let depth_limit = 2 * (usize::BITS - length.leading_zeros());
+
+    introsort_rec(arr, 0, length, depth_limit, is_less, steps);
+}
+
+/// Auxiliary function sorting `arr[lo..hi]` by quicksort until either the partitions are small
+/// enough for insertion sort or the recursion gets too deep, in which case the pivots are
+/// evidently bad and the partition is heap sorted instead.
+fn introsort_rec<T, F, R>(arr: &mut [T], mut lo: usize, mut hi: usize, mut depth_limit: u32, is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    loop {
+        if steps.is_stopped() {
+            return;
+        }
+
+        if hi - lo <= INSERTION_THRESHOLD {
+            insertion_sort_range(arr, lo, hi, is_less, steps);
+            return;
+        }
+
+        steps.record_event(SortEvent::EnterRange(lo, hi));
+
+        if depth_limit == 0 {
+            heap_sort_aux(arr, lo, hi, is_less, steps);
+            return;
+        }
+        depth_limit -= 1;
+
+        let p = median_of_three(arr, lo, hi - 1, is_less, steps);
+        let (lt, gt) = partition(arr, lo, hi - 1, p, is_less, steps);
+
+        // Only recurse into the smaller partition and loop on the larger one.
+        if lt - lo < hi - gt {
+            recurse(steps, |steps| introsort_rec(arr, lo, lt, depth_limit, is_less, steps));
+            lo = gt + 1;
+        } else {
+            recurse(steps, |steps| introsort_rec(arr, gt + 1, hi, depth_limit, is_less, steps));
+            hi = lt;
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt;
+use std::time::Instant;
+use super::recorder::{ Recorder, AllocationRecorder };
+use super::stats::{ SortStats, Counter };
+use super::trace::SortEvent;
+
+/// The limits the limited variants of the sorts abort at, e.g. to bound bogosort or slowsort on
+/// untrusted input.
+///
+/// Every limit is optional, and the default sets none of them:
+///
+/// ```rust
+/// use std::time::{ Duration, Instant };
+/// use sort_it::prelude::*;
+///
+/// let limits = SortLimits {
+///     max_comparisons: Some(1000),
+///     deadline: Some(Instant::now() + Duration::from_millis(100)),
+///     ..SortLimits::default()
+/// };
+///
+/// let mut arr = vec![5, 4, 3, 2, 1];
+/// assert_eq!(arr.bubble_sort_limited(limits), Ok(()));
+/// assert_eq!(arr, [1, 2, 3, 4, 5]);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SortLimits {
+    /// The most steps the sort may take, counting every event of it like the `Steps` iterator
+    /// yields them.
+    pub max_steps: Option<usize>,
+    /// The most comparisons the sort may make.
+    pub max_comparisons: Option<usize>,
+    /// The instant by which the sort has to be done.
+    pub deadline: Option<Instant>,
+}
+
+/// The limit a limited sort exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AbortReason {
+    /// It went on after taking `max_steps` steps.
+    MaxSteps,
+    /// It would have made more than `max_comparisons` comparisons.
+    MaxComparisons,
+    /// It was not done by the `deadline`.
+    Deadline,
+}
+
+impl fmt::Display for AbortReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbortReason::MaxSteps => write!(f, "it exceeded its maximum number of steps"),
+            AbortReason::MaxComparisons => write!(f, "it exceeded its maximum number of comparisons"),
+            AbortReason::Deadline => write!(f, "it exceeded its deadline"),
+        }
+    }
+}
+
+/// The error of a limited sort exceeding its `SortLimits`.
+///
+/// Like a sort stopped by an observer, an aborted sort no longer compares or swaps anything and
+/// returns as soon as the array holds all of its elements again, so `arr` is a partially sorted
+/// permutation of the input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortAborted<T> {
+    /// The limit the sort exceeded.
+    pub reason: AbortReason,
+    /// The array as the sort left it.
+    pub arr: Vec<T>,
+    /// The numbers of operations of the sort up to where it was aborted.
+    pub stats: SortStats,
+}
+
+impl<T> fmt::Display for SortAborted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the sort was aborted because {}", self.reason)
+    }
+}
+
+impl<T> std::error::Error for SortAborted<T>
+    where T: fmt::Debug,
+{}
+
+/// The recorder of the limited variants, counting the events of the sort and stopping it once it
+/// would exceed its limits.
+pub(crate) struct Limited {
+    limits: SortLimits,
+    counter: Counter,
+    steps: usize,
+    reason: Option<AbortReason>,
+}
+
+impl Limited {
+    pub(crate) fn new(limits: SortLimits) -> Self {
+        return Limited { limits, counter: Counter::default(), steps: 0, reason: None };
+    }
+
+    /// Returns whether the sort ran to completion, cloning the partial state of `arr` otherwise,
+    /// like the limited variants of the traits do.
+    pub(crate) fn finish<T>(&self, arr: &[T]) -> Result<(), SortAborted<T>>
+        where T: Clone,
+    {
+        match self.reason {
+            Some(reason) => return Err(SortAborted { reason, arr: arr.to_vec(), stats: self.counter.finish() }),
+            None => return Ok(()),
+        }
+    }
+
+    /// Returns `arr` if the sort ran to completion, or its partial state otherwise, like the
+    /// limited variants of the free functions do.
+    pub(crate) fn finish_vec<T>(&self, arr: Vec<T>) -> Result<Vec<T>, SortAborted<T>> {
+        match self.reason {
+            Some(reason) => return Err(SortAborted { reason, arr, stats: self.counter.finish() }),
+            None => return Ok(arr),
+        }
+    }
+
+    /// Returns the limit the next event would exceed, if any.
+    fn exceeded<T>(&self, event: &SortEvent<&T>) -> Option<AbortReason> {
+        let comparisons = self.counter.finish().comparisons;
+
+        if self.limits.max_steps.is_some_and(|max| self.steps >= max) {
+            return Some(AbortReason::MaxSteps);
+        }
+        if let SortEvent::Compare(..) = event {
+            if self.limits.max_comparisons.is_some_and(|max| comparisons >= max) {
+                return Some(AbortReason::MaxComparisons);
+            }
+        }
+        if self.limits.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Some(AbortReason::Deadline);
+        }
+
+        return None;
+    }
+}
+
+impl AllocationRecorder for Limited {
+    fn record_allocation(&mut self) {
+        self.counter.record_allocation();
+    }
+}
+
+impl<T> Recorder<T> for Limited {
+    fn record(&mut self, _arr: &[T]) {}
+
+    fn record_permuted(&mut self, _arr: &[T], _perm: &[usize]) {}
+
+    fn record_event(&mut self, event: SortEvent<&T>) {
+        if self.reason.is_none() {
+            self.reason = self.exceeded(&event);
+        }
+
+        // A comparison after the sort was aborted is never made, while anything else already
+        // happened by the time it is recorded.
+        if self.reason.is_some() && matches!(event, SortEvent::Compare(..)) {
+            return;
+        }
+
+        self.steps += 1;
+        self.counter.record_event(event);
+    }
+
+    fn record_call(&mut self) {
+        <Counter as Recorder<T>>::record_call(&mut self.counter);
+    }
+
+    fn record_return(&mut self) {
+        <Counter as Recorder<T>>::record_return(&mut self.counter);
+    }
+
+    fn is_recording(&self) -> bool {
+        return false;
+    }
+
+    fn is_stopped(&self) -> bool {
+        return self.reason.is_some();
+    }
+}
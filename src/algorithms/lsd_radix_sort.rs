@@ -0,0 +1,83 @@
+use super::radix_key::{ RadixKey, RadixItem, write_back };
+use super::recorder::Recorder;
+use super::trace::SortEvent;
+
+/// The radix used by LSD radix sort unless another one is given, i.e. one byte per digit.
+pub const DEFAULT_RADIX: usize = 1 << 8;
+
+radix_sort! {
+    /// A trait providing the LSD radix sort method.
+    ///
+    /// Not being a comparison sort, it has no variants taking a comparator, only ones taking a key
+    /// extraction function, whose keys it sorts by instead.
+    /// It orders floats like `total_cmp` does anyway, so it has no `_total` variants either.
+    pub trait LsdRadixSort: lsd_radix_sort, "LSD radix sort", " using a radix of 256";
+    core |arr, steps| lsd_radix_sort_aux(arr, DEFAULT_RADIX, steps);
+
+    fn lsd_radix_sort_with_radix(radix: usize): " with a given radix"
+        where { T: RadixKey, }
+        panics "Panics if `radix` is not a power of two between 2 and 2^16."
+        check |_arr| { assert_radix(radix); }
+        => |arr, steps| lsd_radix_sort_aux(arr, radix, steps);
+}
+
+/// Auxiliary function panicking unless `radix` is a power of two between 2 and 2^16, which is
+/// checked before anything else so arrays too short to be sorted don't let an invalid radix through.
+fn assert_radix(radix: usize) {
+    assert!(
+        radix.is_power_of_two() && (2..=1 << 16).contains(&radix),
+        "the radix of LSD radix sort must be a power of two between 2 and 2^16, got {}", radix,
+    );
+}
+
+/// Auxiliary function distributing the values by every digit, from the least significant to the
+/// most significant one.
+fn lsd_radix_sort_aux<T, R>(arr: &mut [T], radix: usize, steps: &mut R)
+    where T: RadixItem,
+          R: Recorder<T>,
+{
+    let bits = radix.trailing_zeros();
+    let mask = (radix - 1) as u128;
+    let digit = |item: &T, shift: u32| ((item.key() >> shift) & mask) as usize;
+
+    let mut buffer = arr.to_vec();
+    let mut counts = vec![0usize; radix];
+    steps.record_allocation();
+    steps.record_allocation();
+
+    let mut shift = 0;
+    while shift < T::BITS && !steps.is_stopped() {
+        counts.iter_mut().for_each(|count| *count = 0);
+        for (i, item) in arr.iter().enumerate() {
+            steps.record_event(SortEvent::Read(i));
+            counts[digit(item, shift)] += 1;
+        }
+
+        // If all values share the digit, the pass would not move anything.
+        if counts.contains(&arr.len()) {
+            shift += bits;
+            continue;
+        }
+
+        // Turn the counts into the index the first value of every digit goes to.
+        let mut total = 0;
+        for count in counts.iter_mut() {
+            let current = *count;
+            *count = total;
+            total += current;
+        }
+
+        // Distributing the values in their current order keeps the passes stable,
+        // which is what makes the earlier passes count.
+        for (i, item) in arr.iter().enumerate() {
+            steps.record_event(SortEvent::Read(i));
+            let d = digit(item, shift);
+            buffer[counts[d]] = *item;
+            counts[d] += 1;
+        }
+
+        write_back(arr, 0, &buffer, steps);
+
+        shift += bits;
+    }
+}
@@ -1,274 +1,96 @@
-use std::time::{ Instant, Duration };
+use super::recorder::{ Recorder, Permuted, apply_permutation, less_at, recurse };
+use super::trace::SortEvent;
 
-/// A trait providing the merge sort method.
-pub trait MergeSort<T: PartialEq + PartialOrd + Clone + Copy> {
-    /// The merge sort algorithm.
-    /// 
-    /// Sorts the `Vec` it is called on.
-    fn merge_sort(&mut self);
-
-    /// The merge sort algorithm but timed.
-    /// 
-    /// Sorts the `Vec` it is called on and returns the `Duration` of the process.
-    fn merge_sort_timed(&mut self) -> Duration;
-
-    /// The merge sort algorithm but stepped.
-    /// 
-    /// Sorts the `Vec` it is called on and returns a `Vec` containing each step of the process.
-    fn merge_sort_stepped(&mut self) -> Vec<Vec<T>>;
-
-    /// The merge sort algorithm but stepped _and_ timed.
-    /// 
-    /// Sorts the `Vec` it is called on and returns a `Vec` containing each step of the process, 
-    /// including the `Duration` of the entire process.
-    fn merge_sort_stepped_and_timed(&mut self) -> (Vec<Vec<T>>, Duration);
-}
-
-/// The trait implementation of the merge sort algorithm.
-impl<T> MergeSort<T> for Vec<T> 
-    where T: PartialEq + PartialOrd + Clone + Copy,
-{
-    fn merge_sort(&mut self) {
-        // If the array only contains one element, it's sorted by default.
-        if self.len() <= 1 {
-            return;
-        }
-
-        // Obtain the right- and left-hand-sides.
-        let rhs = &self[..self.len()/2];
-        let lhs = &self[self.len()/2..];
-
-        *self = merge_rec(rhs.to_vec(), lhs.to_vec());
-    }
-
-    fn merge_sort_timed(&mut self) -> Duration {
-        let time = Instant::now();
-
-        // If the array only contains one element, it's sorted by default.
-        if self.len() <= 1 {
-            return time.elapsed();
-        }
-
-        // Obtain the right- and left-hand-sides.
-        let rhs = &self[..self.len()/2];
-        let lhs = &self[self.len()/2..];
-
-        *self = merge_rec(rhs.to_vec(), lhs.to_vec());
-
-        return time.elapsed();
-    }
-
-    fn merge_sort_stepped(&mut self) -> Vec<Vec<T>> {
-        let mut steps = vec![self.clone()];
-
-        // If the array only contains one element, it's sorted by default.
-        if self.len() <= 1 {
-            return steps;
-        }
-
-        // Obtain the right- and left-hand-sides.
-        let rhs = &self[..self.len()/2];
-        let lhs = &self[self.len()/2..];
-
-        *self = merge_rec_stepped(rhs.to_vec(), lhs.to_vec(), &mut steps);
-
-        return steps;
-    }
-
-    fn merge_sort_stepped_and_timed(&mut self) -> (Vec<Vec<T>>, Duration) {
-        let time = Instant::now();
-
-        let mut steps = vec![self.clone()];
-
-        // If the array only contains one element, it's sorted by default.
-        if self.len() <= 1 {
-            return (steps, time.elapsed());
-        }
-
-        // Obtain the right- and left-hand-sides.
-        let rhs = &self[..self.len()/2];
-        let lhs = &self[self.len()/2..];
-
-        *self = merge_rec_stepped(rhs.to_vec(), lhs.to_vec(), &mut steps);
-
-        (steps, time.elapsed())
-    }
-}
-
-/// The merge sort algorithm.
-/// 
-/// Sorts the given `Vec` and returns the result.
-pub fn merge_sort<T>(arr: Vec<T>) -> Vec<T> 
-    where T: PartialEq + PartialOrd + Clone + Copy,
-{
-    // If the array only contains one element, it's sorted by default.
-    if arr.len() <= 1 {
-        return arr;
-    }
-
-    // Obtain the right- and left-hand-sides.
-    let rhs = &arr[..arr.len()/2];
-    let lhs = &arr[arr.len()/2..];
-
-    merge_rec(rhs.to_vec(), lhs.to_vec())
-}
-
-/// The merge sort algorithm but timed.
-///
-/// Sorts the given `Vec` and returns the result and the `Duration` of the process.
-pub fn merge_sort_timed<T>(arr: Vec<T>) -> (Vec<T>, Duration) 
-    where T: PartialEq + PartialOrd + Clone + Copy,
-{
-    let time = Instant::now();
-
-    // If the array only contains one element, it's sorted by default.
-    if arr.len() <= 1 {
-        return (arr, time.elapsed());
-    }
-
-    // Obtain the right- and left-hand-sides.
-    let rhs = &arr[..arr.len()/2];
-    let lhs = &arr[arr.len()/2..];
-
-    (merge_rec(rhs.to_vec(), lhs.to_vec()), time.elapsed())
+comparison_sort! {
+    /// A trait providing the merge sort method.
+    pub trait MergeSort: merge_sort, "merge sort";
+    core |arr, is_less, steps| merge_sort_aux(arr, is_less, steps);
 }
 
-/// The merge sort algorithm but stepped.
+/// Auxiliary function merge sorting the indices of `arr` and moving the elements into place
+/// afterwards, so they never have to be cloned.
 ///
-/// Sorts the given `Vec` and returns the result and a `Vec` containing all the steps of the entire 
-/// process.
-pub fn merge_sort_stepped<T>(arr: Vec<T>) -> (Vec<T>, Vec<Vec<T>>)
-    where T: PartialEq + PartialOrd + Clone + Copy,
+/// A step is recorded for every merge, containing just the merged elements.
+pub(super) fn merge_sort_aux<T, F, R>(arr: &mut [T], is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
 {
-    let mut steps = vec![arr.clone()];
-
-    // If the array only contains one element, it's sorted by default.
-    if arr.len() <= 1 {
-        return (arr, steps);
-    }
+    let length = arr.len();
 
     // Obtain the right- and left-hand-sides.
-    let rhs = &arr[..arr.len()/2];
-    let lhs = &arr[arr.len()/2..];
-
-    let sorted = merge_rec_stepped(rhs.to_vec(), lhs.to_vec(), &mut steps);
-
-    (sorted, steps)
+    let rhs = (0..length/2).collect();
+    let lhs = (length/2..length).collect();
+    steps.record_allocation();
+    steps.record_allocation();
+
+    let perm = merge_rec(
+        rhs,
+        lhs,
+        0,
+        &mut |a: &usize, b: &usize| is_less(&arr[*a], &arr[*b]),
+        &mut Permuted { arr, steps },
+    );
+    apply_permutation(arr, perm, &mut ());
 }
 
-/// The merge sort algorithm but stepped _and_ timed.
+/// Auxiliary merge function, moving the elements instead of copying them.
 ///
-/// Sorts the given `Vec` and returns the result and a `Vec` containing all the steps of the
-/// process, including the `Duration` of the entire process.
-pub fn merge_sort_stepped_and_timed<T>(arr: Vec<T>) -> (Vec<T>, Vec<Vec<T>>, Duration) 
-    where T: PartialEq + PartialOrd + Clone + Copy,
+/// The right-hand-side starts at `lo` in the array, directly followed by the left-hand-side.
+fn merge_rec<T, F, R>(mut rhs: Vec<T>, mut lhs: Vec<T>, lo: usize, is_less: &mut F, steps: &mut R) -> Vec<T>
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
 {
-    let time = Instant::now();
-
-    let mut steps = vec![arr.clone()];
-
-    // If the array only contains one element, it's sorted by default.
-    if arr.len() <= 1 {
-        return (arr, steps, time.elapsed());
+    // A stopped sort leaves both sides as they are.
+    if steps.is_stopped() {
+        rhs.append(&mut lhs);
+        return rhs;
     }
 
-    // Obtain the right- and left-hand-sides.
-    let rhs = &arr[..arr.len()/2];
-    let lhs = &arr[arr.len()/2..];
-
-    let sorted = merge_rec_stepped(rhs.to_vec(), lhs.to_vec(), &mut steps);
-
-    (sorted, steps, time.elapsed())
-}
-
-/// Auxiliary merge function.
-fn merge_rec<T>(mut rhs: Vec<T>, mut lhs: Vec<T>) -> Vec<T> 
-    where T: PartialEq + PartialOrd + Clone + Copy,
-{
-    let mut sorted = vec![];
+    let mid = lo + rhs.len();
+    steps.record_event(SortEvent::EnterRange(lo, mid + lhs.len()));
 
     if rhs.len() > 1 {
-        let new_rhs = &rhs[..rhs.len()/2];
-        let new_lhs = &rhs[rhs.len()/2..];
+        let new_lhs = rhs.split_off(rhs.len()/2);
+        steps.record_allocation();
 
-        rhs = merge_rec(new_rhs.to_vec(), new_lhs.to_vec());
+        rhs = recurse(steps, |steps| merge_rec(rhs, new_lhs, lo, is_less, steps));
     }
     if lhs.len() > 1 {
-        let new_rhs = &lhs[..lhs.len()/2];
-        let new_lhs = &lhs[lhs.len()/2..];
-
-        lhs = merge_rec(new_rhs.to_vec(), new_lhs.to_vec());
-    }
-
-    let mut i = 0;
-    let mut j = 0;
+        let new_lhs = lhs.split_off(lhs.len()/2);
+        steps.record_allocation();
 
-    while i < rhs.len() && j < lhs.len() {
-        if rhs[i] <= lhs[j] {
-            sorted.push(rhs[i]);
-            i += 1;
-        } else {
-            sorted.push(lhs[j]);
-            j += 1;
-        }
+        lhs = recurse(steps, |steps| merge_rec(lhs, new_lhs, mid, is_less, steps));
     }
 
-    if i == rhs.len() {
-        for k in j..lhs.len() {
-            sorted.push(lhs[k]);
-        }
-    } else if j == lhs.len() {
-        for k in i..rhs.len() {
-            sorted.push(rhs[k]);
-        }
-    }
+    let mut sorted = Vec::with_capacity(rhs.len() + lhs.len());
+    steps.record_allocation();
+    let mut rhs = rhs.into_iter().peekable();
+    let mut lhs = lhs.into_iter().peekable();
 
-    return sorted;
-}
-
-/// Auxiliary merge function with step support.
-fn merge_rec_stepped<T>(mut rhs: Vec<T>, mut lhs: Vec<T>, steps: &mut Vec<Vec<T>>) -> Vec<T> 
-    where T: PartialEq + PartialOrd + Clone + Copy,
-{
-    let mut sorted = vec![];
-
-    if rhs.len() > 1 {
-        let new_rhs = &rhs[..rhs.len()/2];
-        let new_lhs = &rhs[rhs.len()/2..];
+    // The positions of the next elements of both sides, which stay where they are in the array
+    // until the merge is done.
+    let mut r_at = lo;
+    let mut l_at = mid;
 
-        rhs = merge_rec_stepped(new_rhs.to_vec(), new_lhs.to_vec(), steps);
-    }
-    if lhs.len() > 1 {
-        let new_rhs = &lhs[..lhs.len()/2];
-        let new_lhs = &lhs[lhs.len()/2..];
-
-        lhs = merge_rec_stepped(new_rhs.to_vec(), new_lhs.to_vec(), steps);
-    }
-
-    let mut i = 0;
-    let mut j = 0;
-
-    while i < rhs.len() && j < lhs.len() {
-        if rhs[i] <= lhs[j] {
-            sorted.push(rhs[i]);
-            i += 1;
+    // Taking from the right-hand-side unless the left-hand-side is strictly smaller keeps the
+    // sort stable.
+    while let (Some(r), Some(l)) = (rhs.peek(), lhs.peek()) {
+        if less_at(l, l_at, r, r_at, is_less, steps) {
+            sorted.extend(lhs.next());
+            l_at += 1;
         } else {
-            sorted.push(lhs[j]);
-            j += 1;
+            sorted.extend(rhs.next());
+            r_at += 1;
         }
     }
 
-    if i == rhs.len() {
-        for k in j..lhs.len() {
-            sorted.push(lhs[k]);
-        }
-    } else if j == lhs.len() {
-        for k in i..rhs.len() {
-            sorted.push(rhs[k]);
-        }
-    }
+    sorted.extend(rhs);
+    sorted.extend(lhs);
 
-    steps.push(sorted.clone());
+    for (i, value) in sorted.iter().enumerate() {
+        steps.record_event(SortEvent::Write(lo + i, value));
+    }
+    steps.record(&sorted);
 
     return sorted;
 }
@@ -1,40 +1,119 @@
+use std::cmp::Ordering;
+
+#[macro_use]
+mod variants;
+
+pub mod bogobogosort;
 pub mod bogosort;
+pub mod bozosort;
 pub mod bubble_sort;
+pub mod catalog;
+pub mod cocktail_shaker_sort;
+pub mod comb_sort;
+pub mod counting_sort;
 pub mod gnome_sort;
+pub mod heap_sort;
 pub mod insertion_sort;
+pub mod introsort;
+pub mod limits;
+pub mod lsd_radix_sort;
 pub mod merge_sort;
+pub mod msd_radix_sort;
+pub mod nan_policy;
+pub mod observer;
+pub mod odd_even_sort;
+pub mod pdqsort;
+pub mod permutation_sort;
 pub mod quicksort;
+pub mod radix_key;
 pub mod selection_sort;
+pub mod shell_sort;
 pub mod slowsort;
+pub mod sorter;
+pub mod stats;
+pub mod steps;
 pub mod stooge_sort;
+pub mod timsort;
+pub mod trace;
 pub mod tree_sort;
 
+mod recorder;
+
+pub use bogobogosort::*;
 pub use bogosort::*;
+pub use bozosort::*;
 pub use bubble_sort::*;
+pub use catalog::*;
+pub use cocktail_shaker_sort::*;
+pub use comb_sort::*;
+pub use counting_sort::*;
 pub use gnome_sort::*;
+pub use heap_sort::*;
 pub use insertion_sort::*;
+pub use introsort::*;
+pub use limits::{ SortLimits, AbortReason, SortAborted };
+pub use lsd_radix_sort::*;
 pub use merge_sort::*;
+pub use msd_radix_sort::*;
+pub use nan_policy::{ NanPolicy, NanError, TotalOrder };
+pub use observer::SortObserver;
+pub use odd_even_sort::*;
+pub use pdqsort::*;
+pub use permutation_sort::*;
 pub use quicksort::*;
+pub use radix_key::RadixKey;
 pub use selection_sort::*;
+pub use shell_sort::*;
 pub use slowsort::*;
+pub use sorter::*;
+pub use stats::SortStats;
+pub use steps::{ Step, Steps };
 pub use stooge_sort::*;
+pub use timsort::*;
+pub use trace::*;
 pub use tree_sort::*;
 
-/// A trait providing the `is_sorted` method on `Vec`'s implementing `T`.
-pub trait IsSorted<T: PartialEq + PartialOrd + Clone + Copy> {
-    /// Returns whether the given `Vec` is sorted or not.
+/// A trait providing the `is_sorted` method on slices and `Vec`s of `T`.
+///
+/// The inherent `is_sorted` method of slices takes precedence over the one of this trait, so on
+/// slices and arrays it has to be called as `IsSorted::is_sorted(&arr[..])`.
+pub trait IsSorted<T: PartialEq + PartialOrd> {
+    /// Returns whether the given slice is sorted or not.
+    ///
+    /// Elements which are not comparable, like `NaN` and anything else, are not in order, so a
+    /// slice containing `NaN` next to another element is never sorted.
     fn is_sorted(&self) -> bool;
+
+    /// Returns whether the given slice is sorted under the `NaN` policy `policy` or not, i.e.
+    /// whether the `_with_nans` variants of the sorts leave it as it is.
+    fn is_sorted_with_nans(&self, policy: NanPolicy) -> bool;
 }
 
-/// The trait implementation providing the `is_sorted` method.
-impl<T> IsSorted<T> for Vec<T>
-    where T: PartialEq + PartialOrd + Clone + Copy,
+/// The trait implementation providing the `is_sorted` method on slices.
+impl<T> IsSorted<T> for [T]
+    where T: PartialEq + PartialOrd,
 {
     fn is_sorted(&self) -> bool {
         let mut is_sorted = true;
 
         for i in 1..self.len() {
-            if self[i] < self[i-1] {
+            if !matches!(self[i-1].partial_cmp(&self[i]), Some(Ordering::Less | Ordering::Equal)) {
+                is_sorted = false;
+            }
+        }
+
+        return is_sorted;
+    }
+
+    fn is_sorted_with_nans(&self, policy: NanPolicy) -> bool {
+        if policy.check(self).is_err() {
+            return false;
+        }
+
+        let mut is_sorted = true;
+
+        for i in 1..self.len() {
+            if policy.is_less(&self[i], &self[i-1]) {
                 is_sorted = false;
             }
         }
@@ -43,3 +122,16 @@ impl<T> IsSorted<T> for Vec<T>
     }
 }
 
+/// The trait implementation providing the `is_sorted` method on `Vec`s, which the inherent method
+/// of slices doesn't shadow.
+impl<T> IsSorted<T> for Vec<T>
+    where T: PartialEq + PartialOrd,
+{
+    fn is_sorted(&self) -> bool {
+        return IsSorted::is_sorted(&self[..]);
+    }
+
+    fn is_sorted_with_nans(&self, policy: NanPolicy) -> bool {
+        return self[..].is_sorted_with_nans(policy);
+    }
+}
@@ -0,0 +1,91 @@
+use super::radix_key::{ RadixItem, write_back };
+use super::recorder::{ Recorder, less, swap, recurse };
+use super::trace::SortEvent;
+
+/// The number of bits of the digits MSD radix sort distributes by.
+const DIGIT_BITS: u32 = 8;
+
+/// The size of the buckets below which MSD radix sort switches to insertion sort.
+const INSERTION_THRESHOLD: usize = 32;
+
+radix_sort! {
+    /// A trait providing the MSD radix sort method.
+    ///
+    /// Not being a comparison sort, it has no variants taking a comparator, only ones taking a key
+    /// extraction function, whose keys it sorts by instead.
+    /// It orders floats like `total_cmp` does anyway, so it has no `_total` variants either.
+    pub trait MsdRadixSort: msd_radix_sort, "MSD radix sort";
+    stepped "A step is recorded for every value written back to the array and for every swap of the \
+        insertion sort used for small buckets.";
+    core |arr, steps| msd_radix_sort_aux(arr, steps);
+}
+
+/// Auxiliary function starting the recursion at the most significant digit.
+fn msd_radix_sort_aux<T, R>(arr: &mut [T], steps: &mut R)
+    where T: RadixItem,
+          R: Recorder<T>,
+{
+    let length = arr.len();
+    msd_radix_sort_rec(arr, 0, length, T::BITS - DIGIT_BITS, steps);
+}
+
+/// Auxiliary function distributing `arr[lo..hi]` into buckets by the digit at `shift` and then
+/// sorting every bucket by the next, less significant digit.
+fn msd_radix_sort_rec<T, R>(arr: &mut [T], lo: usize, hi: usize, shift: u32, steps: &mut R)
+    where T: RadixItem,
+          R: Recorder<T>,
+{
+    if steps.is_stopped() {
+        return;
+    }
+    steps.record_event(SortEvent::EnterRange(lo, hi));
+
+    if hi - lo <= INSERTION_THRESHOLD {
+        for i in lo..hi {
+            let mut j = i;
+            while j > lo && less(arr, j, j-1, &mut |a: &T, b: &T| a.key() < b.key(), steps) {
+                swap(arr, j, j-1, steps);
+                j -= 1;
+            }
+        }
+        return;
+    }
+
+    let digit = |item: &T| ((item.key() >> shift) & ((1 << DIGIT_BITS) - 1)) as usize;
+
+    let mut counts = [0usize; 1 << DIGIT_BITS];
+    for (i, item) in arr[lo..hi].iter().enumerate() {
+        steps.record_event(SortEvent::Read(lo + i));
+        counts[digit(item)] += 1;
+    }
+
+    // Turn the counts into the index the first value of every bucket goes to.
+    let mut starts = [0usize; 1 << DIGIT_BITS];
+    let mut total = 0;
+    for (start, count) in starts.iter_mut().zip(counts.iter()) {
+        *start = total;
+        total += count;
+    }
+
+    let mut buffer = arr[lo..hi].to_vec();
+    steps.record_allocation();
+    let mut next = starts;
+    for (i, item) in arr[lo..hi].iter().enumerate() {
+        steps.record_event(SortEvent::Read(lo + i));
+        let d = digit(item);
+        buffer[next[d]] = *item;
+        next[d] += 1;
+    }
+
+    write_back(arr, lo, &buffer, steps);
+
+    if shift == 0 {
+        return;
+    }
+
+    for (start, count) in starts.iter().zip(counts.iter()) {
+        if *count > 1 {
+            recurse(steps, |steps| msd_radix_sort_rec(arr, lo + start, lo + start + count, shift - DIGIT_BITS, steps));
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+use super::radix_key::RadixKey;
+
+/// How the `_with_nans` variants of the sorts handle the elements which are not comparable even to
+/// themselves, like `NaN`.
+///
+/// The plain variants compare with `<` alone, under which such an element is neither less nor
+/// greater than anything, so they leave it wherever it happens to end up and the elements around
+/// it may not be in order either.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NanPolicy {
+    /// Puts them before all other elements.
+    First,
+    /// Puts them after all other elements.
+    #[default]
+    Last,
+    /// Refuses to sort slices containing any of them.
+    Error,
+}
+
+impl NanPolicy {
+    /// Returns a `NanError` for the first element which is not comparable to itself if they are
+    /// not allowed.
+    pub(crate) fn check<T>(self, arr: &[T]) -> Result<(), NanError>
+        where T: PartialOrd,
+    {
+        if self == NanPolicy::Error {
+            if let Some(index) = arr.iter().position(is_nan) {
+                return Err(NanError { index });
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// Returns whether `a` goes before `b`, which is a total order as long as the elements which
+    /// are comparable to themselves are comparable to each other too, like the floats are.
+    pub(crate) fn is_less<T>(self, a: &T, b: &T) -> bool
+        where T: PartialOrd,
+    {
+        return match (is_nan(a), is_nan(b)) {
+            (false, false) => a < b,
+            (true, false) => self == NanPolicy::First,
+            (false, true) => self != NanPolicy::First,
+            (true, true) => false,
+        };
+    }
+
+    /// Returns the key the non-comparison sorts distribute `value` by, which is its own key
+    /// unless it is `NaN`, in which case it gets the smallest or the largest key there is.
+    ///
+    /// The floats only have `NaN`s at either end of their keys anyway, so this keeps the order
+    /// of everything else.
+    pub(crate) fn radix_key<T>(self, value: &T) -> NanKey<T>
+        where T: RadixKey,
+    {
+        let key = if !is_nan(value) {
+            value.radix_key()
+        } else if self == NanPolicy::First {
+            0
+        } else {
+            u128::MAX >> (128 - T::BITS)
+        };
+
+        return NanKey { key, marker: PhantomData };
+    }
+}
+
+/// Returns whether `value` is not comparable to itself, like `NaN`.
+fn is_nan<T>(value: &T) -> bool
+    where T: PartialOrd,
+{
+    return value.partial_cmp(value).is_none();
+}
+
+/// The key of an element under a `NanPolicy`, as the non-comparison sorts distribute it.
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+pub(crate) struct NanKey<T> {
+    key: u128,
+    marker: PhantomData<T>,
+}
+
+impl<T> RadixKey for NanKey<T>
+    where T: RadixKey,
+{
+    const BITS: u32 = T::BITS;
+
+    fn radix_key(&self) -> u128 {
+        self.key
+    }
+}
+
+/// The error of the `_with_nans` variants of the sorts under `NanPolicy::Error`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NanError {
+    /// The index of the first element which is not comparable to itself.
+    pub index: usize,
+}
+
+impl fmt::Display for NanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the element at index {} is not comparable to itself", self.index)
+    }
+}
+
+impl std::error::Error for NanError {}
+
+/// A trait for the types which have a total order besides their partial one, which the `_total`
+/// variants of the sorts order them by.
+///
+/// It is implemented for the floats, whose total order is the one of their `total_cmp`: all
+/// negative `NaN`s first, then the negative numbers, negative zero before positive zero, the
+/// positive numbers and all positive `NaN`s last.
+pub trait TotalOrder {
+    /// Returns the ordering of `self` and `other` in the total order.
+    fn total_cmp(&self, other: &Self) -> Ordering;
+}
+
+macro_rules! impl_total_order_float {
+    ($($t:ty),*) => {$(
+        impl TotalOrder for $t {
+            fn total_cmp(&self, other: &Self) -> Ordering {
+                <$t>::total_cmp(self, other)
+            }
+        }
+    )*};
+}
+
+impl_total_order_float!(f32, f64);
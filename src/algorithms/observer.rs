@@ -0,0 +1,76 @@
+use std::ops::ControlFlow;
+use super::recorder::{ Recorder, AllocationRecorder };
+use super::trace::SortEvent;
+
+/// A trait for the observers the observed variants of the sorts notify of every event as it
+/// happens, e.g. to stream them to a UI or to count them.
+///
+/// Returning `ControlFlow::Break` stops the sort, which then no longer compares or swaps anything
+/// and returns as soon as the slice holds all of its elements again, leaving it partially sorted.
+/// The observed variants return `ControlFlow::Break` in that case too.
+///
+/// It is implemented for closures taking a `SortEvent` and returning a `ControlFlow`.
+pub trait SortObserver<T> {
+    /// Observes an event of the sort, returning whether the sort should go on.
+    fn observe(&mut self, event: SortEvent<&T>) -> ControlFlow<()>;
+}
+
+impl<T, F> SortObserver<T> for F
+    where F: FnMut(SortEvent<&T>) -> ControlFlow<()>,
+{
+    fn observe(&mut self, event: SortEvent<&T>) -> ControlFlow<()> {
+        return self(event);
+    }
+}
+
+impl<T> SortObserver<T> for &mut dyn SortObserver<T> {
+    fn observe(&mut self, event: SortEvent<&T>) -> ControlFlow<()> {
+        return (**self).observe(event);
+    }
+}
+
+/// The recorder of the observed variants, passing the events on to the observer until it stops
+/// the sort.
+pub(crate) struct Observed<O> {
+    observer: O,
+    stopped: bool,
+}
+
+impl<O> Observed<O> {
+    pub(crate) fn new(observer: O) -> Self {
+        return Observed { observer, stopped: false };
+    }
+
+    /// Returns whether the sort ran to completion, like the observed variants do.
+    pub(crate) fn finish(&self) -> ControlFlow<()> {
+        if self.stopped {
+            return ControlFlow::Break(());
+        }
+
+        return ControlFlow::Continue(());
+    }
+}
+
+impl<O> AllocationRecorder for Observed<O> {}
+
+impl<T, O> Recorder<T> for Observed<O>
+    where O: SortObserver<T>,
+{
+    fn record(&mut self, _arr: &[T]) {}
+
+    fn record_permuted(&mut self, _arr: &[T], _perm: &[usize]) {}
+
+    fn record_event(&mut self, event: SortEvent<&T>) {
+        if !self.stopped {
+            self.stopped = self.observer.observe(event).is_break();
+        }
+    }
+
+    fn is_recording(&self) -> bool {
+        return false;
+    }
+
+    fn is_stopped(&self) -> bool {
+        return self.stopped;
+    }
+}
@@ -0,0 +1,35 @@
+use super::recorder::{ Recorder, less, swap };
+
+comparison_sort! {
+    /// A trait providing the odd-even sort method.
+    pub trait OddEvenSort: odd_even_sort, "odd-even sort";
+    stepped "A step is recorded for every swap, like in `bubble_sort_stepped`.";
+    core |arr, is_less, steps| odd_even_sort_aux(arr, is_less, steps);
+}
+
+/// Auxiliary function alternating between comparing the odd and the even pairs.
+fn odd_even_sort_aux<T, F, R>(arr: &mut [T], is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    // The network sorts any input within as many phases as there are elements, i.e. half as many
+    // rounds of two phases, which bounds the rounds even when the comparisons are inconsistent.
+    let mut rounds = arr.len().div_ceil(2);
+
+    let mut is_sorted = false;
+    while !is_sorted && rounds > 0 {
+        is_sorted = true;
+        rounds -= 1;
+
+        // The pairs within a phase are disjoint, which is what makes
+        // the algorithm suitable for parallel hardware.
+        for phase in [1, 0] {
+            for i in (phase..arr.len() - 1).step_by(2) {
+                if less(arr, i+1, i, is_less, steps) {
+                    is_sorted = false;
+                    swap(arr, i, i+1, steps);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,443 @@
+use super::heap_sort::heap_sort_aux;
+use super::recorder::{ Recorder, less, swap, recurse };
+use super::trace::SortEvent;
+
+/// The size of the partitions below which pdqsort switches to insertion sort.
+const MAX_INSERTION: usize = 20;
+
+/// The size of the blocks of the block partitioning, small enough for the offsets to fit a `u8`.
+const BLOCK: usize = 128;
+
+comparison_sort! {
+    /// A trait providing the pattern-defeating quicksort method.
+    pub trait Pdqsort: pdqsort, "pattern-defeating quicksort";
+    stepped "A step is recorded for every change of the array.";
+    core |arr, is_less, steps| pdqsort_aux(arr, is_less, steps);
+}
+
+/// Auxiliary function starting the recursion, allowing `log2(n)` imbalanced partitions before
+/// falling back to heap sort.
+fn pdqsort_aux<T, F, R>(arr: &mut [T], is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    let length = arr.len();
+    let limit = usize::BITS - length.leading_zeros();
+
+    pdqsort_rec(arr, 0, length, is_less, None, limit, steps);
+}
+
+/// Auxiliary function sorting `arr[lo..hi]`.
+///
+/// `pred` is the index of the pivot of the parent partition directly left of the range, if any,
+/// which is not greater than any element of the range.
+fn pdqsort_rec<T, F, R>(arr: &mut [T], mut lo: usize, mut hi: usize, is_less: &mut F, mut pred: Option<usize>, mut limit: u32, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    let mut was_balanced = true;
+    let mut was_partitioned = true;
+
+    loop {
+        if steps.is_stopped() {
+            return;
+        }
+
+        let length = hi - lo;
+
+        if length <= MAX_INSERTION {
+            insertion_sort(arr, lo, hi, is_less, steps);
+            return;
+        }
+
+        steps.record_event(SortEvent::EnterRange(lo, hi));
+
+        // Too many bad pivot choices, so guarantee O(n log n) by heap sorting.
+        if limit == 0 {
+            heap_sort_aux(arr, lo, hi, is_less, steps);
+            return;
+        }
+
+        // An imbalanced partition hints at a pattern, which is broken up by shuffling some elements.
+        if !was_balanced {
+            break_patterns(arr, lo, hi, steps);
+            limit -= 1;
+        }
+
+        let (pivot, likely_sorted) = choose_pivot(arr, lo, hi, is_less, steps);
+
+        // If the last partitioning was balanced and did not shuffle anything and the pivot
+        // selection hints at sorted input, try to finish it off with a few insertions.
+        if was_balanced && was_partitioned && likely_sorted
+            && partial_insertion_sort(arr, lo, hi, is_less, steps)
+        {
+            return;
+        }
+
+        // If the pivot equals the predecessor, it is the smallest element of the range, so the
+        // range is partitioned into the elements equal to it and the rest. This makes inputs
+        // with many duplicates fast.
+        if let Some(pred) = pred {
+            if !less(arr, pred, pivot, is_less, steps) {
+                lo += partition_equal(arr, lo, hi, pivot, is_less, steps);
+                continue;
+            }
+        }
+
+        let (mid, was_p) = partition(arr, lo, hi, pivot, is_less, steps);
+        was_balanced = (mid - lo).min(hi - mid) >= length / 8;
+        was_partitioned = was_p;
+
+        // Only recurse into the smaller partition and loop on the larger one, which keeps the
+        // state of the loop, like the limit of imbalanced partitions, on either side.
+        if mid - lo < hi - mid - 1 {
+            recurse(steps, |steps| pdqsort_rec(arr, lo, mid, is_less, pred, limit, steps));
+            lo = mid + 1;
+            pred = Some(mid);
+        } else {
+            recurse(steps, |steps| pdqsort_rec(arr, mid + 1, hi, is_less, Some(mid), limit, steps));
+            hi = mid;
+        }
+    }
+}
+
+/// Auxiliary function insertion sorting `arr[lo..hi]`.
+fn insertion_sort<T, F, R>(arr: &mut [T], lo: usize, hi: usize, is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    for i in lo + 1..hi {
+        shift_tail(arr, lo, i, is_less, steps);
+    }
+}
+
+/// Auxiliary function moving `arr[i]` to the left until it is not smaller than its predecessor,
+/// with `lo` as the lower bound.
+fn shift_tail<T, F, R>(arr: &mut [T], lo: usize, mut i: usize, is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    while i > lo && less(arr, i, i-1, is_less, steps) {
+        swap(arr, i, i-1, steps);
+        i -= 1;
+    }
+}
+
+/// Auxiliary function moving `arr[i]` to the right until it is not greater than its successor,
+/// with `hi` as the (exclusive) upper bound.
+fn shift_head<T, F, R>(arr: &mut [T], mut i: usize, hi: usize, is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    while i + 1 < hi && less(arr, i+1, i, is_less, steps) {
+        swap(arr, i, i+1, steps);
+        i += 1;
+    }
+}
+
+/// Auxiliary function sorting `arr[lo..hi]` if only a few elements are out of place, fixing at
+/// most five of them. Returns whether the range is sorted.
+fn partial_insertion_sort<T, F, R>(arr: &mut [T], lo: usize, hi: usize, is_less: &mut F, steps: &mut R) -> bool
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    const MAX_STEPS: usize = 5;
+    const SHORTEST_SHIFTING: usize = 50;
+
+    let mut i = lo + 1;
+    for _ in 0..MAX_STEPS {
+        // Find the next pair of adjacent out-of-order elements.
+        while i < hi && !less(arr, i, i-1, is_less, steps) {
+            i += 1;
+        }
+
+        if i == hi {
+            return true;
+        }
+
+        // Shifting elements of short ranges is not worth it.
+        if hi - lo < SHORTEST_SHIFTING {
+            return false;
+        }
+
+        // Swap the pair and move both elements to their place.
+        swap(arr, i - 1, i, steps);
+        shift_tail(arr, lo, i - 1, is_less, steps);
+        shift_head(arr, i, hi, is_less, steps);
+    }
+
+    return false;
+}
+
+/// Auxiliary function swapping a few pseudo-random elements around the middle of `arr[lo..hi]`.
+///
+/// The random numbers are derived from the length, so the sort stays deterministic.
+fn break_patterns<T, R>(arr: &mut [T], lo: usize, hi: usize, steps: &mut R)
+    where R: Recorder<T>,
+{
+    let length = hi - lo;
+    if length < 8 {
+        return;
+    }
+
+    // Xorshift64, as in Marsaglia's paper.
+    let mut seed = length as u64;
+    let mut random = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed as usize
+    };
+
+    let modulus = length.next_power_of_two();
+    let pos = lo + length / 4 * 2;
+
+    for i in 0..3 {
+        let mut other = random() & (modulus - 1);
+        if other >= length {
+            other -= length;
+        }
+        swap(arr, pos - 1 + i, lo + other, steps);
+    }
+}
+
+/// Auxiliary function choosing a pivot of `arr[lo..hi]`, returning its index and whether the range
+/// is likely already sorted.
+///
+/// The pivot is the median of three elements or, for longer ranges, the median of three medians
+/// of three (Tukey's ninther). If the elements turn out to be mostly descending, the range is
+/// reversed first.
+fn choose_pivot<T, F, R>(arr: &mut [T], lo: usize, hi: usize, is_less: &mut F, steps: &mut R) -> (usize, bool)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    const SHORTEST_MEDIAN_OF_MEDIANS: usize = 50;
+    const MAX_SWAPS: usize = 4 * 3;
+
+    let length = hi - lo;
+
+    let mut a = lo + length / 4;
+    let mut b = lo + length / 4 * 2;
+    let mut c = lo + length / 4 * 3;
+
+    // The number of swaps of the indices while sorting them.
+    let mut swaps = 0;
+
+    if length >= 8 {
+        let mut sort2 = |a: &mut usize, b: &mut usize| {
+            if less(arr, *b, *a, is_less, steps) {
+                std::mem::swap(a, b);
+                swaps += 1;
+            }
+        };
+
+        let mut sort3 = |a: &mut usize, b: &mut usize, c: &mut usize| {
+            sort2(a, b);
+            sort2(b, c);
+            sort2(a, b);
+        };
+
+        if length >= SHORTEST_MEDIAN_OF_MEDIANS {
+            let mut sort_adjacent = |a: &mut usize| {
+                let tmp = *a;
+                sort3(&mut (tmp - 1), a, &mut (tmp + 1));
+            };
+
+            sort_adjacent(&mut a);
+            sort_adjacent(&mut b);
+            sort_adjacent(&mut c);
+        }
+
+        sort3(&mut a, &mut b, &mut c);
+    }
+
+    if swaps < MAX_SWAPS {
+        return (b, swaps == 0);
+    }
+
+    // The maximum number of swaps was performed, so the range is likely
+    // descending, or mostly descending, and reversing it makes it sorted.
+    for i in 0..length / 2 {
+        arr.swap(lo + i, hi - 1 - i);
+        steps.record_event(SortEvent::Swap(lo + i, hi - 1 - i));
+    }
+    steps.record(arr);
+
+    return (lo + hi - 1 - b, true);
+}
+
+/// Auxiliary function partitioning `arr[lo..hi]` into the elements smaller than the pivot
+/// followed by the ones not smaller than it. Returns the final index of the pivot and whether the
+/// range was already partitioned.
+fn partition<T, F, R>(arr: &mut [T], lo: usize, hi: usize, pivot: usize, is_less: &mut F, steps: &mut R) -> (usize, bool)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    // Keep the pivot at the start of the range while partitioning the rest.
+    if pivot != lo {
+        swap(arr, lo, pivot, steps);
+    }
+    steps.record_event(SortEvent::Pivot(lo));
+
+    let mut l = lo + 1;
+    let mut r = hi;
+
+    // Skip the elements already on the correct side.
+    while l < r && less(arr, l, lo, is_less, steps) {
+        l += 1;
+    }
+    while l < r && !less(arr, r-1, lo, is_less, steps) {
+        r -= 1;
+    }
+
+    let was_partitioned = l >= r;
+    let mid = l + partition_in_blocks(arr, l, r, lo, is_less, steps) - 1;
+
+    if mid != lo {
+        swap(arr, lo, mid, steps);
+    }
+    steps.record_event(SortEvent::MarkSorted(mid));
+
+    return (mid, was_partitioned);
+}
+
+/// Auxiliary function partitioning `arr[lo..hi]` by the element at `pivot`, outside of the range,
+/// returning the number of elements smaller than the pivot.
+///
+/// This is the block partitioning of BlockQuicksort: the results of the comparisons of a block of
+/// elements from each side are stored as offsets first and the misplaced elements are swapped
+/// afterwards, which avoids branch mispredictions.
+fn partition_in_blocks<T, F, R>(arr: &mut [T], lo: usize, hi: usize, pivot: usize, is_less: &mut F, steps: &mut R) -> usize
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    // The start of the left block and the end of the right block.
+    let mut l = lo;
+    let mut r = hi;
+
+    // The offsets of the misplaced elements of the left block, those not smaller than the pivot.
+    let mut offsets_l = [0u8; BLOCK];
+    let mut start_l = 0;
+    let mut end_l = 0;
+    let mut block_l = BLOCK;
+
+    // The offsets of the misplaced elements of the right block (counted from its end), those
+    // smaller than the pivot.
+    let mut offsets_r = [0u8; BLOCK];
+    let mut start_r = 0;
+    let mut end_r = 0;
+    let mut block_r = BLOCK;
+
+    loop {
+        // Once the remaining range is short, size the blocks to exactly cover it.
+        let is_done = r - l <= 2 * BLOCK;
+
+        if is_done {
+            let mut rem = r - l;
+            if start_l < end_l || start_r < end_r {
+                rem -= BLOCK;
+            }
+
+            if start_l < end_l {
+                block_r = rem;
+            } else if start_r < end_r {
+                block_l = rem;
+            } else {
+                block_l = rem / 2;
+                block_r = rem - block_l;
+            }
+        }
+
+        if start_l == end_l {
+            start_l = 0;
+            end_l = 0;
+            for i in 0..block_l {
+                offsets_l[end_l] = i as u8;
+                end_l += !less(arr, l + i, pivot, is_less, steps) as usize;
+            }
+        }
+
+        if start_r == end_r {
+            start_r = 0;
+            end_r = 0;
+            for i in 0..block_r {
+                offsets_r[end_r] = i as u8;
+                end_r += less(arr, r - 1 - i, pivot, is_less, steps) as usize;
+            }
+        }
+
+        // Swap pairs of misplaced elements of both blocks.
+        let count = (end_l - start_l).min(end_r - start_r);
+        for k in 0..count {
+            let left = l + offsets_l[start_l + k] as usize;
+            let right = r - 1 - offsets_r[start_r + k] as usize;
+            swap(arr, left, right, steps);
+        }
+        start_l += count;
+        start_r += count;
+
+        if start_l == end_l {
+            l += block_l;
+        }
+        if start_r == end_r {
+            r -= block_r;
+        }
+
+        if is_done {
+            break;
+        }
+    }
+
+    // At most one block has misplaced elements left, which are moved to the far end of the
+    // remaining range.
+    if start_l < end_l {
+        while start_l < end_l {
+            end_l -= 1;
+            swap(arr, l + offsets_l[end_l] as usize, r - 1, steps);
+            r -= 1;
+        }
+        return r - lo;
+    }
+
+    while start_r < end_r {
+        end_r -= 1;
+        swap(arr, l, r - 1 - offsets_r[end_r] as usize, steps);
+        l += 1;
+    }
+    return l - lo;
+}
+
+/// Auxiliary function partitioning `arr[lo..hi]` into the elements equal to the pivot followed by
+/// the ones greater than it, assuming none are smaller. Returns the number of equal elements.
+fn partition_equal<T, F, R>(arr: &mut [T], lo: usize, hi: usize, pivot: usize, is_less: &mut F, steps: &mut R) -> usize
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    if pivot != lo {
+        swap(arr, lo, pivot, steps);
+    }
+    steps.record_event(SortEvent::Pivot(lo));
+
+    let mut l = lo + 1;
+    let mut r = hi;
+
+    loop {
+        while l < r && !less(arr, lo, l, is_less, steps) {
+            l += 1;
+        }
+        while l < r && less(arr, lo, r-1, is_less, steps) {
+            r -= 1;
+        }
+
+        if l >= r {
+            break;
+        }
+
+        r -= 1;
+        swap(arr, l, r, steps);
+        l += 1;
+    }
+
+    // Also count the pivot itself.
+    return l - lo;
+}
@@ -0,0 +1,70 @@
+use super::recorder::{ Recorder, less };
+use super::trace::SortEvent;
+
+comparison_sort! {
+    /// A trait providing the permutation sort method.
+    pub trait PermutationSort: permutation_sort, "permutation sort";
+    core |arr, is_less, steps| permutation_sort_aux(arr, is_less, steps);
+}
+
+/// Returns the expected number of permutations permutation sort steps through on a uniformly random
+/// permutation of `n` distinct elements.
+///
+/// The sorted permutation is the first one in lexicographic order, so starting from the `r`-th of
+/// the `n!` permutations, it takes `n! - r` steps to get there, wrapping around after the last
+/// one. That is `(n! - 1) / 2` on average and `n! - 1` at most.
+pub fn permutation_sort_expected_iterations(n: usize) -> f64 {
+    return ((1..=n).map(|k| k as f64).product::<f64>() - 1.0) / 2.0;
+}
+
+/// Auxiliary function stepping through the permutations of the array in lexicographic order until
+/// it is sorted.
+fn permutation_sort_aux<T, F, R>(arr: &mut [T], is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    while !steps.is_stopped() && (1..arr.len()).any(|i| less(arr, i, i-1, is_less, steps)) {
+        next_permutation(arr, is_less, steps);
+    }
+}
+
+/// Auxiliary function rearranging the array into the next of its permutations in lexicographic
+/// order, recording a step for it.
+///
+/// The last permutation, with the elements in descending order, wraps around to the first one,
+/// which is the sorted one.
+fn next_permutation<T, F, R>(arr: &mut [T], is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    // The longest suffix without an ascent already is the last permutation of its elements.
+    let mut i = arr.len() - 1;
+    while i > 0 && !less(arr, i-1, i, is_less, steps) {
+        i -= 1;
+    }
+
+    // The element before it is swapped with the rightmost, i.e. the smallest, greater element in
+    // it, which is at `i` at the latest.
+    let mut j = arr.len() - 1;
+    while i > 0 && j > i && !less(arr, i-1, j, is_less, steps) {
+        j -= 1;
+    }
+    if steps.is_stopped() {
+        return;
+    }
+    if i > 0 {
+        arr.swap(i-1, j);
+        steps.record_event(SortEvent::Swap(i-1, j));
+    }
+
+    // Which leaves the suffix in descending order, so reversing it makes it the first permutation
+    // of its elements.
+    let (mut lo, mut hi) = (i, arr.len() - 1);
+    while lo < hi {
+        arr.swap(lo, hi);
+        steps.record_event(SortEvent::Swap(lo, hi));
+        lo += 1;
+        hi -= 1;
+    }
+    steps.record(arr);
+}
@@ -1,8 +1,171 @@
-///// The quicksort algorithm.
-/////
-///// Sorts the given `Vec` and returns the result.
-//pub fn quicksort<T>(arr: Vec<T>) -> Vec<T> 
-//    where T: PartialEq + PartialOrd + Clone + Copy,
-//{
-//    todo!()
-//}
+use rand::prelude::*;
+use super::recorder::{ Recorder, less, swap, recurse };
+use super::trace::SortEvent;
+
+/// The strategy used by quicksort to pick the pivot of each partition.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PivotStrategy {
+    /// Always picks the first element of the partition.
+    First,
+    /// Always picks the last element of the partition.
+    Last,
+    /// Picks a random element of the partition.
+    ///
+    /// The thread's random number generator picks it, so the runs differ unless the generator is
+    /// given, like `quicksort_with_rng` and `quicksort_with_seed` do.
+    Random,
+    /// Picks the median of the first, middle and last elements of the partition.
+    #[default]
+    MedianOfThree,
+}
+
+comparison_sort! {
+    /// A trait providing the quicksort method.
+    pub trait Quicksort: quicksort, "quicksort", " using the median-of-three pivot strategy";
+    stepped "A step is recorded every time the partitioning moves an element.";
+    core |arr, is_less, steps| {
+        let length = arr.len();
+        quicksort_rec(arr, 0, length - 1, PivotStrategy::default(), &mut rand::thread_rng(), is_less, steps)
+    };
+
+    fn quicksort_with_pivot(pivot: PivotStrategy): " with a given pivot strategy"
+        where { T: PartialEq + PartialOrd, }
+        => |arr, steps| {
+            let length = arr.len();
+            quicksort_rec(arr, 0, length - 1, pivot, &mut rand::thread_rng(), &mut T::lt, steps)
+        };
+
+    fn quicksort_with_rng<G>(rng: &mut G): " with random pivots from a given random number generator"
+        where { T: PartialEq + PartialOrd, G: Rng, }
+        steps {
+            <G> (rng: G) where {
+                T: PartialEq + PartialOrd + Clone + Send + 'static,
+                G: Rng + Send + 'static,
+            } => (&mut rng)
+        }
+        => |arr, steps| {
+            let length = arr.len();
+            quicksort_rec(arr, 0, length - 1, PivotStrategy::Random, rng, &mut T::lt, steps)
+        };
+
+    fn quicksort_with_seed(seed: u64): " with random pivots from a given seed"
+        where { T: PartialEq + PartialOrd, }
+        => |arr, steps| {
+            let length = arr.len();
+            quicksort_rec(arr, 0, length - 1, PivotStrategy::Random, &mut StdRng::seed_from_u64(seed), &mut T::lt, steps)
+        };
+}
+
+/// Auxiliary function sorting `arr[lo..=hi]`, recording the steps if `steps` is given.
+fn quicksort_rec<T, F, G, R>(arr: &mut [T], mut lo: usize, mut hi: usize, pivot: PivotStrategy, rng: &mut G, is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          G: Rng,
+          R: Recorder<T>,
+{
+    // Only recurse into the smaller partition and loop on the larger one,
+    // so the recursion depth stays logarithmic even for bad pivots.
+    while lo < hi && !steps.is_stopped() {
+        steps.record_event(SortEvent::EnterRange(lo, hi + 1));
+
+        let p = choose_pivot(arr, lo, hi, pivot, rng, is_less, steps);
+        let (lt, gt) = partition(arr, lo, hi, p, is_less, steps);
+
+        if lt - lo < hi - gt {
+            if lt > lo {
+                recurse(steps, |steps| quicksort_rec(arr, lo, lt - 1, pivot, rng, is_less, steps));
+            }
+            lo = gt + 1;
+        } else {
+            if gt < hi {
+                recurse(steps, |steps| quicksort_rec(arr, gt + 1, hi, pivot, rng, is_less, steps));
+            }
+            // Nothing goes before the pivot only if everything is equal to it, which is sorted.
+            if lt == lo {
+                break;
+            }
+            hi = lt - 1;
+        }
+    }
+}
+
+/// Auxiliary three-way partition of `arr[lo..=hi]` around the element at `p`, returning the range
+/// `lt..=gt` of the elements equal to the pivot, which are all in their final positions.
+///
+/// Unlike a two-way partition, it keeps the elements equal to the pivot out of both sides, so
+/// arrays with few distinct elements don't make quicksort quadratic.
+pub(super) fn partition<T, F, R>(arr: &mut [T], lo: usize, hi: usize, p: usize, is_less: &mut F, steps: &mut R) -> (usize, usize)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    // Move the pivot to the start of the partition, where the equal elements gather.
+    if p != lo {
+        swap(arr, p, lo, steps);
+    }
+    let mut p = lo;
+    steps.record_event(SortEvent::Pivot(p));
+
+    // `arr[lo..lt]` goes before the pivot, `arr[lt..i]` is equal to it, `arr[gt+1..=hi]` goes after
+    // it and `arr[i..=gt]` is still to be partitioned. The pivot moves along with the equal
+    // elements, so it is always compared with the pivot itself.
+    let mut lt = lo;
+    let mut i = lo + 1;
+    let mut gt = hi;
+    while i <= gt {
+        if less(arr, i, p, is_less, steps) {
+            swap(arr, lt, i, steps);
+            if p == lt {
+                p = i;
+            }
+            lt += 1;
+            i += 1;
+        } else if less(arr, p, i, is_less, steps) {
+            if i != gt {
+                swap(arr, i, gt, steps);
+            }
+            gt -= 1;
+        } else {
+            i += 1;
+        }
+    }
+
+    for k in lt..=gt {
+        steps.record_event(SortEvent::MarkSorted(k));
+    }
+
+    return (lt, gt);
+}
+
+/// Auxiliary function returning the index of the pivot of `arr[lo..=hi]`, drawing it from `rng` for
+/// the random strategy.
+fn choose_pivot<T, F, G, R>(arr: &[T], lo: usize, hi: usize, pivot: PivotStrategy, rng: &mut G, is_less: &mut F, steps: &mut R) -> usize
+    where F: FnMut(&T, &T) -> bool,
+          G: Rng,
+          R: Recorder<T>,
+{
+    match pivot {
+        PivotStrategy::First => lo,
+        PivotStrategy::Last => hi,
+        PivotStrategy::Random => rng.gen_range(lo..=hi),
+        PivotStrategy::MedianOfThree => median_of_three(arr, lo, hi, is_less, steps),
+    }
+}
+
+/// Auxiliary function returning the index of the median of the first, middle and last elements of
+/// `arr[lo..=hi]`.
+pub(super) fn median_of_three<T, F, R>(arr: &[T], lo: usize, hi: usize, is_less: &mut F, steps: &mut R) -> usize
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    let mid = lo + (hi - lo) / 2;
+    let mut less = |i, j| less(arr, i, j, is_less, steps);
+
+    if less(lo, mid) {
+        if less(mid, hi) { mid } else if less(lo, hi) { hi } else { lo }
+    } else if less(lo, hi) {
+        lo
+    } else if less(mid, hi) {
+        hi
+    } else {
+        mid
+    }
+}
@@ -0,0 +1,124 @@
+use super::recorder::{ Recorder, Keyed };
+use super::trace::SortEvent;
+
+/// A trait for the types the non-comparison sorts (counting and radix sort) can sort.
+///
+/// It maps every value to an unsigned key ordered the same way as the values themselves, so the
+/// sorts can work on the bits of the key alone. It is implemented for all primitive integers,
+/// signed or not, and for floats.
+pub trait RadixKey: PartialEq + PartialOrd + Clone + Copy {
+    /// The number of bits of the key.
+    const BITS: u32;
+
+    /// Returns the key of the value, which only uses the lower `Self::BITS` bits.
+    fn radix_key(&self) -> u128;
+}
+
+macro_rules! impl_radix_key_unsigned {
+    ($($t:ty),*) => {$(
+        impl RadixKey for $t {
+            const BITS: u32 = <$t>::BITS;
+
+            fn radix_key(&self) -> u128 {
+                *self as u128
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_radix_key_signed {
+    ($($t:ty => $u:ty),*) => {$(
+        impl RadixKey for $t {
+            const BITS: u32 = <$t>::BITS;
+
+            fn radix_key(&self) -> u128 {
+                // Flipping the sign bit moves the negative numbers below the positive ones.
+                (*self as $u ^ (1 << (<$t>::BITS - 1))) as u128
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_radix_key_float {
+    ($($t:ty),*) => {$(
+        impl RadixKey for $t {
+            const BITS: u32 = std::mem::size_of::<$t>() as u32 * 8;
+
+            fn radix_key(&self) -> u128 {
+                let bits = self.to_bits();
+                let sign = 1 << (<$t as RadixKey>::BITS - 1);
+
+                // Negative floats are ordered backwards by their bits, so all of their bits are
+                // flipped, while positive floats only need to be moved above the negative ones.
+                // This orders the floats like `total_cmp` does.
+                if bits & sign != 0 {
+                    (!bits) as u128
+                } else {
+                    (bits | sign) as u128
+                }
+            }
+        }
+    )*};
+}
+
+impl_radix_key_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_radix_key_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+impl_radix_key_float!(f32, f64);
+
+/// The items the non-comparison sorts distribute: either the values themselves or, when sorting
+/// by a key, the keys paired with the indices of their values.
+pub(super) trait RadixItem: Copy {
+    /// The number of bits of the key.
+    const BITS: u32;
+
+    /// Returns the key the item is sorted by.
+    fn key(&self) -> u128;
+
+    /// Returns whether the two items are the same, which may be the case even though they
+    /// compare unequal (e.g. `NaN`) or not be even though they compare equal (e.g. `-0.0`).
+    fn same(&self, other: &Self) -> bool;
+}
+
+impl<T> RadixItem for T
+    where T: RadixKey,
+{
+    const BITS: u32 = <T as RadixKey>::BITS;
+
+    fn key(&self) -> u128 {
+        self.radix_key()
+    }
+
+    fn same(&self, other: &Self) -> bool {
+        self.radix_key() == other.radix_key()
+    }
+}
+
+impl<K> RadixItem for Keyed<K>
+    where K: RadixKey,
+{
+    const BITS: u32 = K::BITS;
+
+    fn key(&self) -> u128 {
+        self.key.radix_key()
+    }
+
+    fn same(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+/// Auxiliary function copying `buffer` into `arr` from `lo` on, recording a step for every item
+/// which actually changes.
+pub(super) fn write_back<T, R>(arr: &mut [T], lo: usize, buffer: &[T], steps: &mut R)
+    where T: RadixItem,
+          R: Recorder<T>,
+{
+    for (i, item) in buffer.iter().enumerate() {
+        if !arr[lo + i].same(item) {
+            arr[lo + i] = *item;
+
+            steps.record_event(SortEvent::Write(lo + i, item));
+            steps.record(arr);
+        }
+    }
+}
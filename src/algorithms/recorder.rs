@@ -0,0 +1,280 @@
+use std::cmp::Ordering;
+use super::trace::SortEvent;
+
+/// A trait for the places the auxiliary functions record the steps of a sort into.
+///
+/// The non-stepped variants record into `()`, which throws every step away, so only the stepped
+/// variants need to be able to clone the elements.
+pub(crate) trait Recorder<T>: AllocationRecorder {
+    /// Records the current state of the array.
+    fn record(&mut self, arr: &[T]);
+
+    /// Records the state of the array as seen through `perm`, which holds the index in `arr` of the
+    /// element at every position. This is used by the sorts which sort indices instead of moving
+    /// the elements themselves.
+    fn record_permuted(&mut self, arr: &[T], perm: &[usize]);
+
+    /// Records a single event of the sort, which only the traces and the observers keep.
+    fn record_event(&mut self, _event: SortEvent<&T>) {}
+
+    /// Returns whether the steps are kept at all, so recording them is worth any extra work.
+    fn is_recording(&self) -> bool {
+        return true;
+    }
+
+    /// Records that the sort made a recursive call, which it returns from with
+    /// `record_return`.
+    fn record_call(&mut self) {}
+
+    /// Records that the sort returned from a recursive call.
+    fn record_return(&mut self) {}
+
+    /// Returns whether an observer stopped the sort, after which it no longer compares or swaps
+    /// anything and only winds down until the array holds all of its elements again.
+    fn is_stopped(&self) -> bool {
+        return false;
+    }
+}
+
+/// A trait for the places the auxiliary buffers a sort allocates are recorded into, which is
+/// separate from `Recorder` since the sorts recording their steps differently need it too.
+pub(crate) trait AllocationRecorder {
+    /// Records that the sort allocated an auxiliary buffer, which only the counters keep.
+    fn record_allocation(&mut self) {}
+}
+
+impl AllocationRecorder for () {}
+
+impl<T> AllocationRecorder for Vec<Vec<T>> {}
+
+impl<T> Recorder<T> for () {
+    fn record(&mut self, _arr: &[T]) {}
+
+    fn record_permuted(&mut self, _arr: &[T], _perm: &[usize]) {}
+
+    fn is_recording(&self) -> bool {
+        return false;
+    }
+}
+
+impl<T> Recorder<T> for Vec<Vec<T>>
+    where T: Clone,
+{
+    fn record(&mut self, arr: &[T]) {
+        self.push(arr.to_vec());
+    }
+
+    fn record_permuted(&mut self, arr: &[T], perm: &[usize]) {
+        self.push(perm.iter().map(|&i| arr[i].clone()).collect());
+    }
+}
+
+/// A recorder for the steps of sorting the indices `perm` of `arr`, recording the array as seen
+/// through them.
+pub(crate) struct Permuted<'a, T, R> {
+    pub(crate) arr: &'a [T],
+    pub(crate) steps: &'a mut R,
+}
+
+impl<T, R> AllocationRecorder for Permuted<'_, T, R>
+    where R: AllocationRecorder,
+{
+    fn record_allocation(&mut self) {
+        self.steps.record_allocation();
+    }
+}
+
+impl<T, R> Recorder<usize> for Permuted<'_, T, R>
+    where R: Recorder<T>,
+{
+    fn record(&mut self, perm: &[usize]) {
+        self.steps.record_permuted(self.arr, perm);
+    }
+
+    fn record_permuted(&mut self, perm: &[usize], outer: &[usize]) {
+        if self.steps.is_recording() {
+            let perm: Vec<usize> = outer.iter().map(|&i| perm[i]).collect();
+            self.steps.record_permuted(self.arr, &perm);
+        }
+    }
+
+    fn record_event(&mut self, event: SortEvent<&usize>) {
+        self.steps.record_event(event.map(|&i| &self.arr[i]));
+    }
+
+    fn is_recording(&self) -> bool {
+        return self.steps.is_recording();
+    }
+
+    fn record_call(&mut self) {
+        self.steps.record_call();
+    }
+
+    fn record_return(&mut self) {
+        self.steps.record_return();
+    }
+
+    fn is_stopped(&self) -> bool {
+        return self.steps.is_stopped();
+    }
+}
+
+/// A key paired with the index of the value it belongs to, which the sorts by key sort instead of
+/// the values themselves.
+#[derive(Clone, Copy)]
+pub(crate) struct Keyed<K> {
+    pub(crate) key: K,
+    pub(crate) index: usize,
+}
+
+impl<K> Keyed<K>
+    where K: PartialEq + PartialOrd,
+{
+    /// Compares the keys, and the indices for equal keys, which keeps even the unstable sorts
+    /// stable.
+    pub(crate) fn is_less(&self, other: &Self) -> bool {
+        match self.key.partial_cmp(&other.key) {
+            Some(Ordering::Less) => return true,
+            Some(Ordering::Equal) => return self.index < other.index,
+            _ => return false,
+        }
+    }
+}
+
+/// Records the values the keys belong to, in the order of the keys.
+impl<T, K, R> Recorder<Keyed<K>> for Permuted<'_, T, R>
+    where R: Recorder<T>,
+{
+    fn record(&mut self, keyed: &[Keyed<K>]) {
+        if self.steps.is_recording() {
+            let perm: Vec<usize> = keyed.iter().map(|k| k.index).collect();
+            self.steps.record_permuted(self.arr, &perm);
+        }
+    }
+
+    fn record_permuted(&mut self, keyed: &[Keyed<K>], outer: &[usize]) {
+        if self.steps.is_recording() {
+            let perm: Vec<usize> = outer.iter().map(|&i| keyed[i].index).collect();
+            self.steps.record_permuted(self.arr, &perm);
+        }
+    }
+
+    fn record_event(&mut self, event: SortEvent<&Keyed<K>>) {
+        self.steps.record_event(event.map(|keyed| &self.arr[keyed.index]));
+    }
+
+    fn is_recording(&self) -> bool {
+        return self.steps.is_recording();
+    }
+
+    fn record_call(&mut self) {
+        self.steps.record_call();
+    }
+
+    fn record_return(&mut self) {
+        self.steps.record_return();
+    }
+
+    fn is_stopped(&self) -> bool {
+        return self.steps.is_stopped();
+    }
+}
+
+/// Auxiliary function sorting `arr` by the keys `key` returns, by having `sort` sort the keys
+/// paired with the indices of their values and moving the values into place afterwards, so they
+/// never have to be cloned.
+pub(crate) fn sort_keyed<T, K, F, R, S>(arr: &mut [T], key: &mut F, steps: &mut R, sort: S)
+    where F: FnMut(&T) -> K,
+          R: AllocationRecorder,
+          S: FnOnce(&mut [Keyed<K>], &mut Permuted<T, R>),
+{
+    let mut keyed: Vec<Keyed<K>> = arr.iter()
+        .enumerate()
+        .map(|(index, value)| Keyed { key: key(value), index })
+        .collect();
+    steps.record_allocation();
+
+    sort(&mut keyed, &mut Permuted { arr, steps });
+
+    let perm = keyed.into_iter().map(|k| k.index).collect();
+    steps.record_allocation();
+    apply_permutation(arr, perm, &mut ());
+}
+
+/// Auxiliary function moving the element at `perm[i]` to `i` for every `i`, by following the
+/// cycles of the permutation and swapping, so the elements never have to be cloned.
+///
+/// A step is recorded for every swap.
+pub(crate) fn apply_permutation<T, R>(arr: &mut [T], mut perm: Vec<usize>, steps: &mut R)
+    where R: Recorder<T>,
+{
+    for start in 0..perm.len() {
+        let mut current = start;
+
+        // The element which belongs at `current` is swapped in, carrying the displaced element
+        // along the cycle until it reaches the position it belongs at.
+        while perm[current] != start {
+            let next = perm[current];
+            swap(arr, current, next, steps);
+
+            perm[current] = current;
+            current = next;
+        }
+        perm[current] = current;
+    }
+}
+
+/// Auxiliary function returning whether the element at `i` goes before the one at `j`, recording
+/// the comparison.
+pub(crate) fn less<T, F, R>(arr: &[T], i: usize, j: usize, is_less: &mut F, steps: &mut R) -> bool
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    return less_at(&arr[i], i, &arr[j], j, is_less, steps);
+}
+
+/// Auxiliary function returning whether `a`, read from `i`, goes before `b`, read from `j`,
+/// recording the comparison.
+///
+/// Once the sort is stopped, nothing goes before anything anymore, so it winds down without
+/// calling `is_less` again.
+pub(crate) fn less_at<T, F, R>(a: &T, i: usize, b: &T, j: usize, is_less: &mut F, steps: &mut R) -> bool
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    steps.record_event(SortEvent::Compare(i, j));
+
+    if steps.is_stopped() {
+        return false;
+    }
+
+    return is_less(a, b);
+}
+
+/// Auxiliary function swapping the elements at `i` and `j`, recording the swap and the step.
+///
+/// Once the sort is stopped, the elements stay where they are.
+pub(crate) fn swap<T, R>(arr: &mut [T], i: usize, j: usize, steps: &mut R)
+    where R: Recorder<T>,
+{
+    if steps.is_stopped() {
+        return;
+    }
+
+    arr.swap(i, j);
+
+    steps.record_event(SortEvent::Swap(i, j));
+    steps.record(arr);
+}
+
+/// Auxiliary function making the recursive call `call`, recording the call and the return.
+pub(crate) fn recurse<T, R, U, C>(steps: &mut R, call: C) -> U
+    where R: Recorder<T>,
+          C: FnOnce(&mut R) -> U,
+{
+    steps.record_call();
+    let result = call(steps);
+    steps.record_return();
+
+    return result;
+}
@@ -1,242 +1,157 @@
-use std::time::{ Instant, Duration };
-
-/// A trait providing the selection sort algorithm.
-pub trait SelectionSort<T: PartialEq + PartialOrd + Clone + Copy> {
-    /// The selection sort algorithm.
-    ///
-    /// Sorts the `Vec` it is called on.
-    fn selection_sort(&mut self);
-
-    /// The selection sort algorithm but timed.
-    ///
-    /// Sorts the `Vec` it is called on and returns the `Duration` of the process.
-    fn selection_sort_timed(&mut self) -> Duration;
-
-    /// The selection sort algorithm but stepped.
-    ///
-    /// Sorts the `Vec` it is called on and returns a `Vec` containing each step of the process.
-    fn selection_sort_stepped(&mut self) -> Vec<(Vec<T>, Vec<T>)>;
-
-    /// The selection sort algorithm but stepped _and_ timed.
-    ///
-    /// Sorts the `Vec` it is called on and returns a `Vec` containing each step of the process,
-    /// including the `Duration` of the entire process.
-    fn selection_sort_stepped_and_timed(&mut self) -> (Vec<(Vec<T>, Vec<T>)>, Duration);
+use super::recorder::{ Recorder, AllocationRecorder, Permuted, Keyed };
+use super::trace::{ SortEvent, SortTrace };
+use super::observer::{ SortObserver, Observed };
+use super::stats::Counter;
+use super::limits::Limited;
+
+/// The steps of a selection sort, each being a tuple of the unsorted and sorted parts of the array.
+pub type SelectionSteps<T> = Vec<(Vec<T>, Vec<T>)>;
+
+comparison_sort! {
+    /// A trait providing the selection sort algorithm.
+    pub trait SelectionSort: selection_sort, "selection sort";
+    steps SelectionSteps<T> = |arr| vec![(arr.to_vec(), vec![])];
+    stepped "Each step is a tuple of the unsorted and sorted array.";
+    core |arr, is_less, steps| selection_sort_aux(arr, is_less, steps);
 }
 
-/// The trait implementation of the selection sort algorithm.
-impl<T> SelectionSort<T> for Vec<T>
-    where T: PartialEq + PartialOrd + Clone + Copy,
+/// Auxiliary function repeatedly moving the minimum of the unsorted part to its front.
+fn selection_sort_aux<T, F, R>(arr: &mut [T], is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: SelectionRecorder<T>,
 {
-    fn selection_sort(&mut self) {
-        if self.len() <= 1 {
+    for i in 0..arr.len() {
+        if steps.is_selection_stopped() {
             return;
         }
 
-        let mut sorted = vec![];
-
-        while self.len() > 0 {
-            let mut min_idx = 0;
-            for i in 0..self.len() {
-                if self[min_idx] > self[i] {
-                    min_idx = i;
-                }
+        let mut min_idx = i;
+        for j in i..arr.len() {
+            steps.record_selection_event(SortEvent::Compare(j, min_idx));
+            if !steps.is_selection_stopped() && is_less(&arr[j], &arr[min_idx]) {
+                min_idx = j;
             }
-            sorted.push(self.remove(min_idx));
         }
 
-        *self = sorted;
-    }
-
-    fn selection_sort_timed(&mut self) -> Duration {
-        let time = Instant::now();
-
-        if self.len() <= 1 {
-            return time.elapsed();
+        // Rotating the minimum to the front, rather than swapping it there, keeps the order
+        // of the unsorted part the same as removing it would.
+        arr[i..=min_idx].rotate_right(1);
+        for j in (i..min_idx).rev() {
+            steps.record_selection_event(SortEvent::Swap(j, j + 1));
         }
-
-        let mut sorted = vec![];
-
-        while self.len() > 0 {
-            let mut min_idx = 0;
-            for i in 0..self.len() {
-                if self[min_idx] > self[i] {
-                    min_idx = i;
-                }
-            }
-            sorted.push(self.remove(min_idx));
-        }
-
-        *self = sorted;
-
-        return time.elapsed();
+        steps.record_selection_event(SortEvent::MarkSorted(i));
+        steps.record_selection(arr, i + 1);
     }
+}
 
-    fn selection_sort_stepped(&mut self) -> Vec<(Vec<T>, Vec<T>)> {
-        let mut steps = vec![(self.clone(), vec![])];
-
-        if self.len() <= 1 {
-            return steps;
-        }
-
-        let mut sorted = vec![];
+/// A place to record the steps of a selection sort into, which unlike the other sorts records
+/// the unsorted and sorted parts of the array separately.
+trait SelectionRecorder<T>: AllocationRecorder {
+    /// Records the array, of which the first `sorted` elements are sorted.
+    fn record_selection(&mut self, arr: &[T], sorted: usize);
 
-        while self.len() > 0 {
-            let mut min_idx = 0;
-            for i in 0..self.len() {
-                if self[min_idx] > self[i] {
-                    min_idx = i;
-                }
-            }
-            sorted.push(self.remove(min_idx));
-            steps.push((self.clone(), sorted.clone()));
-        }
+    /// Records the array as seen through `perm`, like `Recorder::record_permuted`.
+    fn record_selection_permuted(&mut self, arr: &[T], perm: &[usize], sorted: usize);
 
-        *self = sorted;
+    /// Records a single event of the sort, like `Recorder::record_event`.
+    fn record_selection_event(&mut self, _event: SortEvent<&T>) {}
 
-        return steps;
+    /// Returns whether an observer stopped the sort, like `Recorder::is_stopped`.
+    fn is_selection_stopped(&self) -> bool {
+        return false;
     }
+}
 
-    fn selection_sort_stepped_and_timed(&mut self) -> (Vec<(Vec<T>, Vec<T>)>, Duration) {
-        let time = Instant::now();
-
-        let mut steps = vec![(self.clone(), vec![])];
-
-        if self.len() <= 1 {
-            return (steps, time.elapsed());
-        }
-
-        let mut sorted = vec![];
-
-        while self.len() > 0 {
-            let mut min_idx = 0;
-            for i in 0..self.len() {
-                if self[min_idx] > self[i] {
-                    min_idx = i;
-                }
-            }
-            sorted.push(self.remove(min_idx));
-            steps.push((self.clone(), sorted.clone()));
-        }
-
-        *self = sorted;
+impl<T> SelectionRecorder<T> for () {
+    fn record_selection(&mut self, _arr: &[T], _sorted: usize) {}
 
-        (steps, time.elapsed())
-    }
+    fn record_selection_permuted(&mut self, _arr: &[T], _perm: &[usize], _sorted: usize) {}
 }
 
-/// The selection sort algorithm.
-///
-/// Sorts the given `Vec` and returns the result.
-pub fn selection_sort<T>(mut arr: Vec<T>) -> Vec<T>
-    where T: PartialEq + PartialOrd + Clone + Copy,
+impl<T> SelectionRecorder<T> for SelectionSteps<T>
+    where T: Clone,
 {
-    if arr.len() <= 1 {
-        return arr;
+    fn record_selection(&mut self, arr: &[T], sorted: usize) {
+        self.push((arr[sorted..].to_vec(), arr[..sorted].to_vec()));
     }
 
-    let mut sorted = vec![];
-
-    while arr.len() > 0 {
-        let mut min_idx = 0;
-        for i in 0..arr.len() {
-            if arr[min_idx] > arr[i] {
-                min_idx = i;
-            }
-        }
-        sorted.push(arr.remove(min_idx));
+    fn record_selection_permuted(&mut self, arr: &[T], perm: &[usize], sorted: usize) {
+        let values = |perm: &[usize]| perm.iter().map(|&i| arr[i].clone()).collect();
+        self.push((values(&perm[sorted..]), values(&perm[..sorted])));
     }
-
-    return sorted;
 }
 
-/// The selection sort algorithm but timed.
-///
-/// Sorts the given `Vec` and returns the result and the `Duration` of the entire process.
-pub fn selection_sort_timed<T>(mut arr: Vec<T>) -> (Vec<T>, Duration)
-    where T: PartialEq + PartialOrd + Clone + Copy,
+impl<T> SelectionRecorder<T> for SortTrace<T>
+    where T: Clone,
 {
-    let time = Instant::now();
+    fn record_selection(&mut self, _arr: &[T], _sorted: usize) {}
 
-    if arr.len() <= 1 {
-        return (arr, time.elapsed());
-    }
-
-    let mut sorted = vec![];
+    fn record_selection_permuted(&mut self, _arr: &[T], _perm: &[usize], _sorted: usize) {}
 
-    while arr.len() > 0 {
-        let mut min_idx = 0;
-        for i in 0..arr.len() {
-            if arr[min_idx] > arr[i] {
-                min_idx = i;
-            }
-        }
-        sorted.push(arr.remove(min_idx));
+    fn record_selection_event(&mut self, event: SortEvent<&T>) {
+        self.push(event.map(T::clone));
     }
-
-    (sorted, time.elapsed())
 }
 
-/// The selection sort algorithm but stepped.
-///
-/// Sorts the given `Vec` and returns the result and a `Vec` containing the steps of the 
-/// process as a tuple of the unsorted and sorted array.
-pub fn selection_sort_stepped<T>(mut arr: Vec<T>) -> (Vec<T>, Vec<(Vec<T>, Vec<T>)>)
-    where T: PartialEq + PartialOrd + Clone + Copy,
-{
-    let mut steps = vec![(arr.clone(), vec![])];
+impl<T> SelectionRecorder<T> for Counter {
+    fn record_selection(&mut self, _arr: &[T], _sorted: usize) {}
 
-    if arr.len() <= 1 {
-        return (arr, steps);
+    fn record_selection_permuted(&mut self, _arr: &[T], _perm: &[usize], _sorted: usize) {}
+
+    fn record_selection_event(&mut self, event: SortEvent<&T>) {
+        self.record_event(event);
     }
+}
 
-    let mut sorted = vec![];
+impl<T> SelectionRecorder<T> for Limited {
+    fn record_selection(&mut self, _arr: &[T], _sorted: usize) {}
 
-    while arr.len() > 0 {
-        let mut min_idx = 0;
-        for i in 0..arr.len() {
-            if arr[min_idx] > arr[i] {
-                min_idx = i;
-            }
-        }
+    fn record_selection_permuted(&mut self, _arr: &[T], _perm: &[usize], _sorted: usize) {}
 
-        sorted.push(arr.remove(min_idx));
-        steps.push((arr.clone(), sorted.clone()));
+    fn record_selection_event(&mut self, event: SortEvent<&T>) {
+        self.record_event(event);
     }
 
-    (sorted, steps)
+    fn is_selection_stopped(&self) -> bool {
+        return <Self as Recorder<T>>::is_stopped(self);
+    }
 }
 
-/// The selection sort algorithm but stepped _and_ timed.
-///
-/// Sorts the given `Vec` and returns the result and a `Vec` containing the steps of the 
-/// process as a tuple of the unsorted and sorted array, including the `Duration` of the 
-/// entire process.
-pub fn selection_sort_stepped_and_timed<T>(mut arr: Vec<T>) -> (Vec<T>, Vec<(Vec<T>, Vec<T>)>, Duration)
-    where T: PartialEq + PartialOrd + Clone + Copy,
+impl<T, O> SelectionRecorder<T> for Observed<O>
+    where O: SortObserver<T>,
 {
-    let time = Instant::now();
+    fn record_selection(&mut self, _arr: &[T], _sorted: usize) {}
 
-    let mut steps = vec![(arr.clone(), vec![])];
+    fn record_selection_permuted(&mut self, _arr: &[T], _perm: &[usize], _sorted: usize) {}
 
-    if arr.len() <= 1 {
-        return (arr, steps, time.elapsed());
+    fn record_selection_event(&mut self, event: SortEvent<&T>) {
+        self.record_event(event);
     }
 
-    let mut sorted = vec![];
+    fn is_selection_stopped(&self) -> bool {
+        return <Self as Recorder<T>>::is_stopped(self);
+    }
+}
 
-    while arr.len() > 0 {
-        let mut min_idx = 0;
-        for i in 0..arr.len() {
-            if arr[min_idx] > arr[i] {
-                min_idx = i;
-            }
-        }
+/// Records the values the keys belong to.
+impl<T, K, R> SelectionRecorder<Keyed<K>> for Permuted<'_, T, R>
+    where R: SelectionRecorder<T>,
+{
+    fn record_selection(&mut self, keyed: &[Keyed<K>], sorted: usize) {
+        let perm: Vec<usize> = keyed.iter().map(|k| k.index).collect();
+        self.steps.record_selection_permuted(self.arr, &perm, sorted);
+    }
 
-        sorted.push(arr.remove(min_idx));
-        steps.push((arr.clone(), sorted.clone()));
+    fn record_selection_permuted(&mut self, keyed: &[Keyed<K>], outer: &[usize], sorted: usize) {
+        let perm: Vec<usize> = outer.iter().map(|&i| keyed[i].index).collect();
+        self.steps.record_selection_permuted(self.arr, &perm, sorted);
     }
 
-    (sorted, steps, time.elapsed())
-}
+    fn record_selection_event(&mut self, event: SortEvent<&Keyed<K>>) {
+        self.steps.record_selection_event(event.map(|keyed| &self.arr[keyed.index]));
+    }
 
+    fn is_selection_stopped(&self) -> bool {
+        return self.steps.is_selection_stopped();
+    }
+}
@@ -0,0 +1,143 @@
+use super::recorder::{ Recorder, less, swap };
+
+/// The sequence of gaps used by shell sort.
+///
+/// Every sequence ends with a gap of one, which is a plain insertion sort.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GapSequence<'a> {
+    /// Shell's original sequence: `n/2, n/4, ..., 1`.
+    Shell,
+    /// Knuth's sequence: `1, 4, 13, 40, ...`, i.e. `(3^k - 1) / 2`.
+    Knuth,
+    /// Sedgewick's sequence: `1, 8, 23, 77, 281, ...`, i.e. `4^k + 3 * 2^(k-1) + 1`.
+    Sedgewick,
+    /// Ciura's experimentally derived sequence: `1, 4, 10, 23, 57, 132, 301, 701`, extended
+    /// by a factor of 2.25 for larger inputs.
+    #[default]
+    Ciura,
+    /// Tokuda's sequence: `1, 4, 9, 20, 46, 103, ...`, i.e. `ceil((9 * (9/4)^(k-1) - 4) / 5)`.
+    Tokuda,
+    /// A user provided sequence, in any order.
+    ///
+    /// Gaps of zero or not smaller than the input are ignored and a final gap of one is
+    /// added if it is missing, so the result is always sorted.
+    Custom(&'a [usize]),
+}
+
+impl GapSequence<'_> {
+    /// Returns the gaps used to sort `n` elements, from the largest down to one.
+    pub fn gaps(&self, n: usize) -> Vec<usize> {
+        let mut gaps = match *self {
+            GapSequence::Shell => {
+                let mut gaps = vec![];
+                let mut gap = n / 2;
+                while gap > 0 {
+                    gaps.push(gap);
+                    gap /= 2;
+                }
+                gaps
+            }
+            GapSequence::Knuth => generate(n, 1, |h| h.checked_mul(3)?.checked_add(1)),
+            GapSequence::Sedgewick => {
+                let mut gaps = vec![1];
+                for k in 1.. {
+                    let gap = 4usize.checked_pow(k)
+                        .and_then(|p| p.checked_add(3 * (1 << (k - 1)) + 1));
+                    match gap {
+                        Some(gap) if gap < n => gaps.push(gap),
+                        _ => break,
+                    }
+                }
+                gaps
+            }
+            GapSequence::Ciura => {
+                let mut gaps = vec![];
+                for gap in [1, 4, 10, 23, 57, 132, 301, 701] {
+                    if gap >= n {
+                        break;
+                    }
+                    gaps.push(gap);
+                }
+                if let Some(&last) = gaps.last().filter(|&&last| last == 701) {
+                    gaps.extend(generate(n, last, |h| Some((h as f64 * 2.25) as usize)).into_iter().skip(1));
+                }
+                gaps
+            }
+            GapSequence::Tokuda => {
+                let mut gaps = vec![];
+                for k in 1.. {
+                    let gap = ((9.0 * 2.25f64.powi(k - 1) - 4.0) / 5.0).ceil();
+                    if gap >= n as f64 {
+                        break;
+                    }
+                    gaps.push(gap as usize);
+                }
+                gaps
+            }
+            GapSequence::Custom(custom) => {
+                let mut gaps: Vec<usize> = custom.iter()
+                    .copied()
+                    .filter(|&gap| gap > 0 && gap < n)
+                    .collect();
+                gaps.sort_unstable();
+                gaps.dedup();
+                if gaps.first() != Some(&1) {
+                    gaps.insert(0, 1);
+                }
+                gaps
+            }
+        };
+
+        // All sequences except Shell's are generated in increasing order.
+        if gaps.first() < gaps.last() {
+            gaps.reverse();
+        }
+
+        return gaps;
+    }
+}
+
+/// Auxiliary function generating a sequence from `start` while the gaps are smaller than `n`.
+fn generate(n: usize, start: usize, next: impl Fn(usize) -> Option<usize>) -> Vec<usize> {
+    let mut gaps = vec![];
+    let mut gap = Some(start);
+
+    while let Some(h) = gap.filter(|&h| h < n) {
+        gaps.push(h);
+        gap = next(h);
+    }
+
+    return gaps;
+}
+
+comparison_sort! {
+    /// A trait providing the shell sort method.
+    pub trait ShellSort: shell_sort, "shell sort", " using Ciura's gap sequence";
+    core |arr, is_less, steps| shell_sort_aux(arr, GapSequence::default(), is_less, steps);
+
+    fn shell_sort_with_gaps(gaps: GapSequence): " with a given gap sequence"
+        where { T: PartialEq + PartialOrd, }
+        steps {
+            <> (gaps: GapSequence<'static>) where { T: PartialEq + PartialOrd + Clone + Send + 'static, } => (gaps)
+        }
+        => |arr, steps| shell_sort_aux(arr, gaps, &mut T::lt, steps);
+}
+
+/// Auxiliary function running a gapped insertion sort for every gap of the sequence.
+fn shell_sort_aux<T, F, R>(arr: &mut [T], gaps: GapSequence, is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    let gaps = gaps.gaps(arr.len());
+    steps.record_allocation();
+
+    for gap in gaps {
+        for i in gap..arr.len() {
+            let mut j = i;
+            while j >= gap && less(arr, j, j-gap, is_less, steps) {
+                swap(arr, j, j-gap, steps);
+                j -= gap;
+            }
+        }
+    }
+}
@@ -0,0 +1,589 @@
+use std::fmt;
+use std::ops::ControlFlow;
+use std::str::FromStr;
+use std::time::Duration;
+use super::*;
+
+/// A trait providing the eight ways of running a sorting algorithm under the same names for every
+/// algorithm, so it can be picked at runtime, e.g. through `Algorithm::sorter`.
+///
+/// Unlike the traits of the algorithms themselves, it is object safe.
+pub trait Sorter<T> {
+    /// Returns the algorithm the sorter runs.
+    fn algorithm(&self) -> Algorithm;
+
+    /// Sorts the given slice.
+    fn sort(&self, arr: &mut [T]);
+
+    /// Sorts the given slice and returns the `Duration` of the process.
+    fn sort_timed(&self, arr: &mut [T]) -> Duration;
+
+    /// Sorts the given slice and returns a `Vec` containing each step of the process.
+    ///
+    /// Every step is the whole array, even for the algorithms whose own stepped variants record
+    /// something else, like selection sort and tree sort.
+    fn sort_stepped(&self, arr: &mut [T]) -> Vec<Vec<T>>;
+
+    /// Sorts the given slice and returns a `Vec` containing each step of the process, including
+    /// the `Duration` of the entire process.
+    fn sort_stepped_and_timed(&self, arr: &mut [T]) -> (Vec<Vec<T>>, Duration);
+
+    /// Sorts the given slice and returns a `Vec` containing every event of the process, which
+    /// `replay` rebuilds the steps from.
+    fn sort_traced(&self, arr: &mut [T]) -> SortTrace<T>;
+
+    /// Sorts the given slice while notifying `observer` of every event of the process, which may
+    /// stop it early, and returns whether it ran to completion.
+    fn sort_observed(&self, arr: &mut [T], observer: &mut dyn SortObserver<T>) -> ControlFlow<()>;
+
+    /// Sorts the given slice and returns the `SortStats` of the process.
+    fn sort_counted(&self, arr: &mut [T]) -> SortStats;
+
+    /// Sorts the given slice unless the process exceeds `limits`, which aborts it.
+    fn sort_limited(&self, arr: &mut [T], limits: SortLimits) -> Result<(), SortAborted<T>>;
+}
+
+/// The sorting algorithms this crate provides, one for every module of `algorithms`.
+///
+/// It displays as the name of the module, e.g. `gnome_sort`, which is also what it parses from,
+/// ignoring case and accepting spaces or dashes instead of underscores, e.g. `Gnome Sort`, besides
+/// the human readable name of its `AlgorithmInfo`, e.g. `Pattern-Defeating Quicksort`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    Bogobogosort,
+    Bogosort,
+    Bozosort,
+    BubbleSort,
+    CocktailShakerSort,
+    CombSort,
+    CountingSort,
+    GnomeSort,
+    HeapSort,
+    InsertionSort,
+    Introsort,
+    LsdRadixSort,
+    MergeSort,
+    MsdRadixSort,
+    OddEvenSort,
+    Pdqsort,
+    PermutationSort,
+    Quicksort,
+    SelectionSort,
+    ShellSort,
+    Slowsort,
+    StoogeSort,
+    Timsort,
+    TreeSort,
+}
+
+impl Algorithm {
+    /// All of the algorithms, in alphabetical order.
+    pub const ALL: [Algorithm; 24] = [
+        Algorithm::Bogobogosort,
+        Algorithm::Bogosort,
+        Algorithm::Bozosort,
+        Algorithm::BubbleSort,
+        Algorithm::CocktailShakerSort,
+        Algorithm::CombSort,
+        Algorithm::CountingSort,
+        Algorithm::GnomeSort,
+        Algorithm::HeapSort,
+        Algorithm::InsertionSort,
+        Algorithm::Introsort,
+        Algorithm::LsdRadixSort,
+        Algorithm::MergeSort,
+        Algorithm::MsdRadixSort,
+        Algorithm::OddEvenSort,
+        Algorithm::Pdqsort,
+        Algorithm::PermutationSort,
+        Algorithm::Quicksort,
+        Algorithm::SelectionSort,
+        Algorithm::ShellSort,
+        Algorithm::Slowsort,
+        Algorithm::StoogeSort,
+        Algorithm::Timsort,
+        Algorithm::TreeSort,
+    ];
+
+    /// Returns the name of the module of the algorithm.
+    pub fn name(self) -> &'static str {
+        return match self {
+            Algorithm::Bogobogosort => "bogobogosort",
+            Algorithm::Bogosort => "bogosort",
+            Algorithm::Bozosort => "bozosort",
+            Algorithm::BubbleSort => "bubble_sort",
+            Algorithm::CocktailShakerSort => "cocktail_shaker_sort",
+            Algorithm::CombSort => "comb_sort",
+            Algorithm::CountingSort => "counting_sort",
+            Algorithm::GnomeSort => "gnome_sort",
+            Algorithm::HeapSort => "heap_sort",
+            Algorithm::InsertionSort => "insertion_sort",
+            Algorithm::Introsort => "introsort",
+            Algorithm::LsdRadixSort => "lsd_radix_sort",
+            Algorithm::MergeSort => "merge_sort",
+            Algorithm::MsdRadixSort => "msd_radix_sort",
+            Algorithm::OddEvenSort => "odd_even_sort",
+            Algorithm::Pdqsort => "pdqsort",
+            Algorithm::PermutationSort => "permutation_sort",
+            Algorithm::Quicksort => "quicksort",
+            Algorithm::SelectionSort => "selection_sort",
+            Algorithm::ShellSort => "shell_sort",
+            Algorithm::Slowsort => "slowsort",
+            Algorithm::StoogeSort => "stooge_sort",
+            Algorithm::Timsort => "timsort",
+            Algorithm::TreeSort => "tree_sort",
+        };
+    }
+
+    /// Returns whether the algorithm sorts by comparing the elements, which lets it sort anything
+    /// with a partial order, rather than by the bits of their `RadixKey`.
+    pub fn is_comparison_sort(self) -> bool {
+        return !matches!(
+            self,
+            Algorithm::CountingSort | Algorithm::LsdRadixSort | Algorithm::MsdRadixSort
+        );
+    }
+
+    /// Returns a sorter running the algorithm on any elements with a partial order, or `None` for
+    /// the non-comparison sorts (counting and radix sort), which need a `RadixKey`.
+    ///
+    /// The algorithms with options, like quicksort with its pivot strategy, run with the default
+    /// ones.
+    pub fn sorter<T>(self) -> Option<Box<dyn Sorter<T>>>
+        where T: PartialEq + PartialOrd + Clone,
+    {
+        if !self.is_comparison_sort() {
+            return None;
+        }
+
+        return Some(Box::new(ComparisonSorter(self)));
+    }
+
+    /// Returns a sorter running the algorithm on numbers, i.e. elements with a `RadixKey`, which
+    /// every algorithm can sort.
+    ///
+    /// The algorithms with options, like quicksort with its pivot strategy, run with the default
+    /// ones.
+    pub fn numeric_sorter<T>(self) -> Box<dyn Sorter<T>>
+        where T: RadixKey,
+    {
+        return Box::new(NumericSorter(self));
+    }
+
+    /// Returns the methods of the trait of the algorithm, or `None` for the non-comparison sorts.
+    fn methods<T>(self) -> Option<Methods<T>>
+        where T: PartialEq + PartialOrd + Clone,
+    {
+        return Some(match self {
+            Algorithm::Bogobogosort => Methods {
+                sort: <[T]>::bogobogosort,
+                sort_timed: <[T]>::bogobogosort_timed,
+                sort_stepped: <[T]>::bogobogosort_stepped,
+                sort_stepped_and_timed: <[T]>::bogobogosort_stepped_and_timed,
+                sort_traced: <[T]>::bogobogosort_traced,
+                sort_observed: |arr, observer| arr.bogobogosort_observed(observer),
+                sort_counted: <[T]>::bogobogosort_counted,
+                sort_limited: <[T]>::bogobogosort_limited,
+            },
+            Algorithm::Bogosort => Methods {
+                sort: <[T]>::bogosort,
+                sort_timed: <[T]>::bogosort_timed,
+                sort_stepped: <[T]>::bogosort_stepped,
+                sort_stepped_and_timed: <[T]>::bogosort_stepped_and_timed,
+                sort_traced: <[T]>::bogosort_traced,
+                sort_observed: |arr, observer| arr.bogosort_observed(observer),
+                sort_counted: <[T]>::bogosort_counted,
+                sort_limited: <[T]>::bogosort_limited,
+            },
+            Algorithm::Bozosort => Methods {
+                sort: <[T]>::bozosort,
+                sort_timed: <[T]>::bozosort_timed,
+                sort_stepped: <[T]>::bozosort_stepped,
+                sort_stepped_and_timed: <[T]>::bozosort_stepped_and_timed,
+                sort_traced: <[T]>::bozosort_traced,
+                sort_observed: |arr, observer| arr.bozosort_observed(observer),
+                sort_counted: <[T]>::bozosort_counted,
+                sort_limited: <[T]>::bozosort_limited,
+            },
+            Algorithm::BubbleSort => Methods {
+                sort: <[T]>::bubble_sort,
+                sort_timed: <[T]>::bubble_sort_timed,
+                sort_stepped: <[T]>::bubble_sort_stepped,
+                sort_stepped_and_timed: <[T]>::bubble_sort_stepped_and_timed,
+                sort_traced: <[T]>::bubble_sort_traced,
+                sort_observed: |arr, observer| arr.bubble_sort_observed(observer),
+                sort_counted: <[T]>::bubble_sort_counted,
+                sort_limited: <[T]>::bubble_sort_limited,
+            },
+            Algorithm::CocktailShakerSort => Methods {
+                sort: <[T]>::cocktail_shaker_sort,
+                sort_timed: <[T]>::cocktail_shaker_sort_timed,
+                sort_stepped: <[T]>::cocktail_shaker_sort_stepped,
+                sort_stepped_and_timed: <[T]>::cocktail_shaker_sort_stepped_and_timed,
+                sort_traced: <[T]>::cocktail_shaker_sort_traced,
+                sort_observed: |arr, observer| arr.cocktail_shaker_sort_observed(observer),
+                sort_counted: <[T]>::cocktail_shaker_sort_counted,
+                sort_limited: <[T]>::cocktail_shaker_sort_limited,
+            },
+            Algorithm::CombSort => Methods {
+                sort: <[T]>::comb_sort,
+                sort_timed: <[T]>::comb_sort_timed,
+                sort_stepped: <[T]>::comb_sort_stepped,
+                sort_stepped_and_timed: <[T]>::comb_sort_stepped_and_timed,
+                sort_traced: <[T]>::comb_sort_traced,
+                sort_observed: |arr, observer| arr.comb_sort_observed(observer),
+                sort_counted: <[T]>::comb_sort_counted,
+                sort_limited: <[T]>::comb_sort_limited,
+            },
+            Algorithm::GnomeSort => Methods {
+                sort: <[T]>::gnome_sort,
+                sort_timed: <[T]>::gnome_sort_timed,
+                sort_stepped: <[T]>::gnome_sort_stepped,
+                sort_stepped_and_timed: <[T]>::gnome_sort_stepped_and_timed,
+                sort_traced: <[T]>::gnome_sort_traced,
+                sort_observed: |arr, observer| arr.gnome_sort_observed(observer),
+                sort_counted: <[T]>::gnome_sort_counted,
+                sort_limited: <[T]>::gnome_sort_limited,
+            },
+            Algorithm::HeapSort => Methods {
+                sort: <[T]>::heap_sort,
+                sort_timed: <[T]>::heap_sort_timed,
+                sort_stepped: <[T]>::heap_sort_stepped,
+                sort_stepped_and_timed: <[T]>::heap_sort_stepped_and_timed,
+                sort_traced: <[T]>::heap_sort_traced,
+                sort_observed: |arr, observer| arr.heap_sort_observed(observer),
+                sort_counted: <[T]>::heap_sort_counted,
+                sort_limited: <[T]>::heap_sort_limited,
+            },
+            Algorithm::InsertionSort => Methods {
+                sort: <[T]>::insertion_sort,
+                sort_timed: <[T]>::insertion_sort_timed,
+                sort_stepped: <[T]>::insertion_sort_stepped,
+                sort_stepped_and_timed: <[T]>::insertion_sort_stepped_and_timed,
+                sort_traced: <[T]>::insertion_sort_traced,
+                sort_observed: |arr, observer| arr.insertion_sort_observed(observer),
+                sort_counted: <[T]>::insertion_sort_counted,
+                sort_limited: <[T]>::insertion_sort_limited,
+            },
+            Algorithm::Introsort => Methods {
+                sort: <[T]>::introsort,
+                sort_timed: <[T]>::introsort_timed,
+                sort_stepped: <[T]>::introsort_stepped,
+                sort_stepped_and_timed: <[T]>::introsort_stepped_and_timed,
+                sort_traced: <[T]>::introsort_traced,
+                sort_observed: |arr, observer| arr.introsort_observed(observer),
+                sort_counted: <[T]>::introsort_counted,
+                sort_limited: <[T]>::introsort_limited,
+            },
+            Algorithm::MergeSort => Methods {
+                sort: <[T]>::merge_sort,
+                sort_timed: <[T]>::merge_sort_timed,
+                sort_stepped: <[T]>::merge_sort_stepped,
+                sort_stepped_and_timed: <[T]>::merge_sort_stepped_and_timed,
+                sort_traced: <[T]>::merge_sort_traced,
+                sort_observed: |arr, observer| arr.merge_sort_observed(observer),
+                sort_counted: <[T]>::merge_sort_counted,
+                sort_limited: <[T]>::merge_sort_limited,
+            },
+            Algorithm::OddEvenSort => Methods {
+                sort: <[T]>::odd_even_sort,
+                sort_timed: <[T]>::odd_even_sort_timed,
+                sort_stepped: <[T]>::odd_even_sort_stepped,
+                sort_stepped_and_timed: <[T]>::odd_even_sort_stepped_and_timed,
+                sort_traced: <[T]>::odd_even_sort_traced,
+                sort_observed: |arr, observer| arr.odd_even_sort_observed(observer),
+                sort_counted: <[T]>::odd_even_sort_counted,
+                sort_limited: <[T]>::odd_even_sort_limited,
+            },
+            Algorithm::Pdqsort => Methods {
+                sort: <[T]>::pdqsort,
+                sort_timed: <[T]>::pdqsort_timed,
+                sort_stepped: <[T]>::pdqsort_stepped,
+                sort_stepped_and_timed: <[T]>::pdqsort_stepped_and_timed,
+                sort_traced: <[T]>::pdqsort_traced,
+                sort_observed: |arr, observer| arr.pdqsort_observed(observer),
+                sort_counted: <[T]>::pdqsort_counted,
+                sort_limited: <[T]>::pdqsort_limited,
+            },
+            Algorithm::PermutationSort => Methods {
+                sort: <[T]>::permutation_sort,
+                sort_timed: <[T]>::permutation_sort_timed,
+                sort_stepped: <[T]>::permutation_sort_stepped,
+                sort_stepped_and_timed: <[T]>::permutation_sort_stepped_and_timed,
+                sort_traced: <[T]>::permutation_sort_traced,
+                sort_observed: |arr, observer| arr.permutation_sort_observed(observer),
+                sort_counted: <[T]>::permutation_sort_counted,
+                sort_limited: <[T]>::permutation_sort_limited,
+            },
+            Algorithm::Quicksort => Methods {
+                sort: <[T]>::quicksort,
+                sort_timed: <[T]>::quicksort_timed,
+                sort_stepped: <[T]>::quicksort_stepped,
+                sort_stepped_and_timed: <[T]>::quicksort_stepped_and_timed,
+                sort_traced: <[T]>::quicksort_traced,
+                sort_observed: |arr, observer| arr.quicksort_observed(observer),
+                sort_counted: <[T]>::quicksort_counted,
+                sort_limited: <[T]>::quicksort_limited,
+            },
+            Algorithm::SelectionSort => Methods {
+                sort: <[T]>::selection_sort,
+                sort_timed: <[T]>::selection_sort_timed,
+                sort_stepped: |arr| selection_snapshots(arr.selection_sort_stepped()),
+                sort_stepped_and_timed: |arr| {
+                    let (steps, time) = arr.selection_sort_stepped_and_timed();
+                    return (selection_snapshots(steps), time);
+                },
+                sort_traced: <[T]>::selection_sort_traced,
+                sort_observed: |arr, observer| arr.selection_sort_observed(observer),
+                sort_counted: <[T]>::selection_sort_counted,
+                sort_limited: <[T]>::selection_sort_limited,
+            },
+            Algorithm::ShellSort => Methods {
+                sort: <[T]>::shell_sort,
+                sort_timed: <[T]>::shell_sort_timed,
+                sort_stepped: <[T]>::shell_sort_stepped,
+                sort_stepped_and_timed: <[T]>::shell_sort_stepped_and_timed,
+                sort_traced: <[T]>::shell_sort_traced,
+                sort_observed: |arr, observer| arr.shell_sort_observed(observer),
+                sort_counted: <[T]>::shell_sort_counted,
+                sort_limited: <[T]>::shell_sort_limited,
+            },
+            Algorithm::Slowsort => Methods {
+                sort: <[T]>::slowsort,
+                sort_timed: <[T]>::slowsort_timed,
+                sort_stepped: <[T]>::slowsort_stepped,
+                sort_stepped_and_timed: <[T]>::slowsort_stepped_and_timed,
+                sort_traced: <[T]>::slowsort_traced,
+                sort_observed: |arr, observer| arr.slowsort_observed(observer),
+                sort_counted: <[T]>::slowsort_counted,
+                sort_limited: <[T]>::slowsort_limited,
+            },
+            Algorithm::StoogeSort => Methods {
+                sort: <[T]>::stooge_sort,
+                sort_timed: <[T]>::stooge_sort_timed,
+                sort_stepped: <[T]>::stooge_sort_stepped,
+                sort_stepped_and_timed: <[T]>::stooge_sort_stepped_and_timed,
+                sort_traced: <[T]>::stooge_sort_traced,
+                sort_observed: |arr, observer| arr.stooge_sort_observed(observer),
+                sort_counted: <[T]>::stooge_sort_counted,
+                sort_limited: <[T]>::stooge_sort_limited,
+            },
+            Algorithm::Timsort => Methods {
+                sort: <[T]>::timsort,
+                sort_timed: <[T]>::timsort_timed,
+                sort_stepped: <[T]>::timsort_stepped,
+                sort_stepped_and_timed: <[T]>::timsort_stepped_and_timed,
+                sort_traced: <[T]>::timsort_traced,
+                sort_observed: |arr, observer| arr.timsort_observed(observer),
+                sort_counted: <[T]>::timsort_counted,
+                sort_limited: <[T]>::timsort_limited,
+            },
+            Algorithm::TreeSort => Methods {
+                sort: <[T]>::tree_sort,
+                sort_timed: <[T]>::tree_sort_timed,
+                sort_stepped: |arr| tree_snapshots(arr.tree_sort_stepped()),
+                sort_stepped_and_timed: |arr| {
+                    let (steps, time) = arr.tree_sort_stepped_and_timed();
+                    return (tree_snapshots(steps), time);
+                },
+                sort_traced: <[T]>::tree_sort_traced,
+                sort_observed: |arr, observer| arr.tree_sort_observed(observer),
+                sort_counted: <[T]>::tree_sort_counted,
+                sort_limited: <[T]>::tree_sort_limited,
+            },
+            Algorithm::CountingSort | Algorithm::LsdRadixSort | Algorithm::MsdRadixSort => {
+                return None;
+            },
+        });
+    }
+
+    /// Returns the methods of the trait of the algorithm for numbers, which every algorithm has.
+    fn numeric_methods<T>(self) -> Methods<T>
+        where T: RadixKey,
+    {
+        return match self {
+            Algorithm::CountingSort => Methods {
+                sort: <[T]>::counting_sort,
+                sort_timed: <[T]>::counting_sort_timed,
+                sort_stepped: <[T]>::counting_sort_stepped,
+                sort_stepped_and_timed: <[T]>::counting_sort_stepped_and_timed,
+                sort_traced: <[T]>::counting_sort_traced,
+                sort_observed: |arr, observer| arr.counting_sort_observed(observer),
+                sort_counted: <[T]>::counting_sort_counted,
+                sort_limited: <[T]>::counting_sort_limited,
+            },
+            Algorithm::LsdRadixSort => Methods {
+                sort: <[T]>::lsd_radix_sort,
+                sort_timed: <[T]>::lsd_radix_sort_timed,
+                sort_stepped: <[T]>::lsd_radix_sort_stepped,
+                sort_stepped_and_timed: <[T]>::lsd_radix_sort_stepped_and_timed,
+                sort_traced: <[T]>::lsd_radix_sort_traced,
+                sort_observed: |arr, observer| arr.lsd_radix_sort_observed(observer),
+                sort_counted: <[T]>::lsd_radix_sort_counted,
+                sort_limited: <[T]>::lsd_radix_sort_limited,
+            },
+            Algorithm::MsdRadixSort => Methods {
+                sort: <[T]>::msd_radix_sort,
+                sort_timed: <[T]>::msd_radix_sort_timed,
+                sort_stepped: <[T]>::msd_radix_sort_stepped,
+                sort_stepped_and_timed: <[T]>::msd_radix_sort_stepped_and_timed,
+                sort_traced: <[T]>::msd_radix_sort_traced,
+                sort_observed: |arr, observer| arr.msd_radix_sort_observed(observer),
+                sort_counted: <[T]>::msd_radix_sort_counted,
+                sort_limited: <[T]>::msd_radix_sort_limited,
+            },
+            _ => self.methods().unwrap(),
+        };
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = ParseAlgorithmError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalize = |name: &str| name.trim().to_lowercase().replace([' ', '-'], "_");
+        let name = normalize(s);
+
+        let found = Algorithm::ALL.iter().find(|algorithm| {
+            return algorithm.name() == name || normalize(algorithm.info().name) == name;
+        });
+        return match found {
+            Some(&algorithm) => Ok(algorithm),
+            None => Err(ParseAlgorithmError { name: s.to_string() }),
+        };
+    }
+}
+
+/// The error of parsing an `Algorithm` from a name which is not one of any algorithm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseAlgorithmError {
+    /// The name which was parsed.
+    pub name: String,
+}
+
+impl fmt::Display for ParseAlgorithmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "there is no sorting algorithm called `{}`", self.name)
+    }
+}
+
+impl std::error::Error for ParseAlgorithmError {}
+
+/// The methods of the trait of an algorithm, with its steps turned into whole arrays.
+struct Methods<T> {
+    sort: fn(&mut [T]),
+    sort_timed: fn(&mut [T]) -> Duration,
+    sort_stepped: fn(&mut [T]) -> Vec<Vec<T>>,
+    sort_stepped_and_timed: fn(&mut [T]) -> (Vec<Vec<T>>, Duration),
+    sort_traced: fn(&mut [T]) -> SortTrace<T>,
+    sort_observed: fn(&mut [T], &mut dyn SortObserver<T>) -> ControlFlow<()>,
+    sort_counted: fn(&mut [T]) -> SortStats,
+    sort_limited: fn(&mut [T], SortLimits) -> Result<(), SortAborted<T>>,
+}
+
+/// The sorter of a comparison sort.
+struct ComparisonSorter(Algorithm);
+
+impl<T> Sorter<T> for ComparisonSorter
+    where T: PartialEq + PartialOrd + Clone,
+{
+    fn algorithm(&self) -> Algorithm {
+        return self.0;
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        (self.0.methods().unwrap().sort)(arr);
+    }
+
+    fn sort_timed(&self, arr: &mut [T]) -> Duration {
+        return (self.0.methods().unwrap().sort_timed)(arr);
+    }
+
+    fn sort_stepped(&self, arr: &mut [T]) -> Vec<Vec<T>> {
+        return (self.0.methods().unwrap().sort_stepped)(arr);
+    }
+
+    fn sort_stepped_and_timed(&self, arr: &mut [T]) -> (Vec<Vec<T>>, Duration) {
+        return (self.0.methods().unwrap().sort_stepped_and_timed)(arr);
+    }
+
+    fn sort_traced(&self, arr: &mut [T]) -> SortTrace<T> {
+        return (self.0.methods().unwrap().sort_traced)(arr);
+    }
+
+    fn sort_observed(&self, arr: &mut [T], observer: &mut dyn SortObserver<T>) -> ControlFlow<()> {
+        return (self.0.methods().unwrap().sort_observed)(arr, observer);
+    }
+
+    fn sort_counted(&self, arr: &mut [T]) -> SortStats {
+        return (self.0.methods().unwrap().sort_counted)(arr);
+    }
+
+    fn sort_limited(&self, arr: &mut [T], limits: SortLimits) -> Result<(), SortAborted<T>> {
+        return (self.0.methods().unwrap().sort_limited)(arr, limits);
+    }
+}
+
+/// The sorter of any algorithm sorting numbers.
+struct NumericSorter(Algorithm);
+
+impl<T> Sorter<T> for NumericSorter
+    where T: RadixKey,
+{
+    fn algorithm(&self) -> Algorithm {
+        return self.0;
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        (self.0.numeric_methods().sort)(arr);
+    }
+
+    fn sort_timed(&self, arr: &mut [T]) -> Duration {
+        return (self.0.numeric_methods().sort_timed)(arr);
+    }
+
+    fn sort_stepped(&self, arr: &mut [T]) -> Vec<Vec<T>> {
+        return (self.0.numeric_methods().sort_stepped)(arr);
+    }
+
+    fn sort_stepped_and_timed(&self, arr: &mut [T]) -> (Vec<Vec<T>>, Duration) {
+        return (self.0.numeric_methods().sort_stepped_and_timed)(arr);
+    }
+
+    fn sort_traced(&self, arr: &mut [T]) -> SortTrace<T> {
+        return (self.0.numeric_methods().sort_traced)(arr);
+    }
+
+    fn sort_observed(&self, arr: &mut [T], observer: &mut dyn SortObserver<T>) -> ControlFlow<()> {
+        return (self.0.numeric_methods().sort_observed)(arr, observer);
+    }
+
+    fn sort_counted(&self, arr: &mut [T]) -> SortStats {
+        return (self.0.numeric_methods().sort_counted)(arr);
+    }
+
+    fn sort_limited(&self, arr: &mut [T], limits: SortLimits) -> Result<(), SortAborted<T>> {
+        return (self.0.numeric_methods().sort_limited)(arr, limits);
+    }
+}
+
+/// Turns the steps of a selection sort into whole arrays, the sorted part coming first.
+fn selection_snapshots<T>(steps: SelectionSteps<T>) -> Vec<Vec<T>> {
+    return steps.into_iter()
+        .map(|(unsorted, mut sorted)| {
+            sorted.extend(unsorted);
+            return sorted;
+        })
+        .collect();
+}
+
+/// Turns the steps of a tree sort into whole arrays, dropping the contents of the tree.
+fn tree_snapshots<T>(steps: TreeSortSteps<T>) -> Vec<Vec<T>> {
+    return steps.into_iter().map(|(_, arr)| arr).collect();
+}
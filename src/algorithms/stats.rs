@@ -0,0 +1,75 @@
+use super::recorder::{ Recorder, AllocationRecorder };
+use super::trace::SortEvent;
+
+/// The numbers of operations of a sort, as the counted variants of the sorts return them.
+///
+/// Unlike the `Duration` the timed variants return, they only depend on the input, so they are the
+/// same on every run and every machine, as long as the sort is not randomized.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SortStats {
+    /// The number of comparisons, i.e. how often the comparator was called.
+    pub comparisons: usize,
+    /// The number of swaps of two elements.
+    pub swaps: usize,
+    /// The number of elements read on their own, like the non-comparison sorts read the keys and
+    /// the merges copy elements aside.
+    pub reads: usize,
+    /// The number of elements written on their own, like the merges write them back, which does not
+    /// include the swaps.
+    pub writes: usize,
+    /// The most recursive calls the sort was in at once, which is 0 for the sorts which do not
+    /// recurse at all.
+    pub recursion_depth: usize,
+    /// The number of auxiliary buffers the sort allocated, like the ones the merges copy elements
+    /// into.
+    pub allocations: usize,
+}
+
+/// The recorder of the counted variants, counting the events of the sort.
+#[derive(Default)]
+pub(crate) struct Counter {
+    stats: SortStats,
+    depth: usize,
+}
+
+impl Counter {
+    /// Returns the numbers of operations of the sort, like the counted variants do.
+    pub(crate) fn finish(&self) -> SortStats {
+        return self.stats;
+    }
+}
+
+impl AllocationRecorder for Counter {
+    fn record_allocation(&mut self) {
+        self.stats.allocations += 1;
+    }
+}
+
+impl<T> Recorder<T> for Counter {
+    fn record(&mut self, _arr: &[T]) {}
+
+    fn record_permuted(&mut self, _arr: &[T], _perm: &[usize]) {}
+
+    fn record_event(&mut self, event: SortEvent<&T>) {
+        match event {
+            SortEvent::Compare(..) => self.stats.comparisons += 1,
+            SortEvent::Swap(..) => self.stats.swaps += 1,
+            SortEvent::Read(..) => self.stats.reads += 1,
+            SortEvent::Write(..) => self.stats.writes += 1,
+            _ => {},
+        }
+    }
+
+    fn record_call(&mut self) {
+        self.depth += 1;
+        self.stats.recursion_depth = self.stats.recursion_depth.max(self.depth);
+    }
+
+    fn record_return(&mut self) {
+        self.depth -= 1;
+    }
+
+    fn is_recording(&self) -> bool {
+        return false;
+    }
+}
@@ -0,0 +1,102 @@
+use std::ops::ControlFlow;
+use std::sync::mpsc::{ self, Receiver };
+use std::thread::{ self, JoinHandle };
+use super::observer::SortObserver;
+use super::trace::SortEvent;
+
+/// A step of a sort, as the lazily stepped variants of the sorts yield them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step<T> {
+    /// The event of the step.
+    pub event: SortEvent<T>,
+    /// The state of the array after the event.
+    pub arr: Vec<T>,
+}
+
+/// An iterator over the steps of a sort, one for every event, as the lazily stepped variants of
+/// the sorts return it.
+///
+/// The sort runs on a thread of its own, which only goes on to the next event once the iterator
+/// is advanced to it, so the steps are never collected and the sort does not get further than
+/// the iterator does. Dropping the iterator stops the sort, like an observer returning
+/// `ControlFlow::Break` would, and waits for the thread to end, so only an iterator which is leaked
+/// rather than dropped leaves the thread waiting for good.
+///
+/// That comes at a cost the other variants don't have:
+///
+/// - Every iterator spawns a thread, which sorts a copy of the array, so the elements and whatever
+///   the sort takes, like a comparator, have to be `Send + 'static`.
+/// - Every event is cloned to be sent to the iterator, and every step clones the whole array, so
+///   the elements have to be `Clone`, and every step takes `O(n)` on top of handing the event over
+///   from one thread to the other.
+///
+/// If the sort panics, e.g. because the comparator does, the iterator panics with the same
+/// payload once it gets there.
+pub struct Steps<T> {
+    arr: Vec<T>,
+    events: Option<Receiver<SortEvent<T>>>,
+    sort: Option<JoinHandle<()>>,
+}
+
+impl<T> Steps<T>
+    where T: Clone + Send + 'static,
+{
+    /// Starts sorting a copy of `arr` with `sort`, which gets the observer to notify of every
+    /// event. Whatever `sort` returns is thrown away, as the steps already tell how it went.
+    pub(crate) fn new<S, U>(arr: Vec<T>, sort: S) -> Self
+        where S: FnOnce(&mut [T], &mut dyn SortObserver<T>) -> U + Send + 'static,
+    {
+        // Without a buffer, every event waits for the iterator to take it.
+        let (sender, events) = mpsc::sync_channel(0);
+
+        let mut copy = arr.clone();
+        let handle = thread::spawn(move || {
+            let _ = sort(&mut copy, &mut |event: SortEvent<&T>| {
+                match sender.send(event.map(T::clone)) {
+                    Ok(()) => return ControlFlow::Continue(()),
+                    Err(_) => return ControlFlow::Break(()),
+                }
+            });
+        });
+
+        return Steps { arr, events: Some(events), sort: Some(handle) };
+    }
+}
+
+impl<T> Iterator for Steps<T>
+    where T: Clone,
+{
+    type Item = Step<T>;
+
+    fn next(&mut self) -> Option<Step<T>> {
+        let event = match self.events.as_ref()?.recv() {
+            Ok(event) => event,
+            Err(_) => {
+                // The sort is done, or it panicked.
+                self.events = None;
+                if let Some(Err(payload)) = self.sort.take().map(JoinHandle::join) {
+                    std::panic::resume_unwind(payload);
+                }
+                return None;
+            },
+        };
+
+        match &event {
+            SortEvent::Swap(i, j) => self.arr.swap(*i, *j),
+            SortEvent::Write(i, value) => self.arr[*i] = value.clone(),
+            _ => {},
+        }
+
+        return Some(Step { event, arr: self.arr.clone() });
+    }
+}
+
+impl<T> Drop for Steps<T> {
+    fn drop(&mut self) {
+        // Hanging up makes the sort stop at its next event, so it can be waited for.
+        self.events = None;
+        if let Some(handle) = self.sort.take() {
+            let _ = handle.join();
+        }
+    }
+}
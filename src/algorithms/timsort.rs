@@ -0,0 +1,594 @@
+use super::recorder::{ Recorder, Permuted, apply_permutation, less, less_at };
+use super::trace::SortEvent;
+
+/// Inputs shorter than this are a single run, extended by binary insertion sort.
+const MIN_MERGE: usize = 64;
+
+/// The number of consecutive wins of one run after which merging switches to galloping.
+const MIN_GALLOP: usize = 7;
+
+comparison_sort! {
+    /// A trait providing the timsort method.
+    pub trait Timsort: timsort, "timsort";
+    stepped "A step is recorded every time a run has been found (and possibly extended or reversed) \
+        and after every merge of two runs.";
+    core |arr, is_less, steps| timsort_aux(arr, is_less, steps);
+}
+
+/// A run of already sorted elements, `arr[base..base + len]`.
+#[derive(Clone, Copy)]
+struct Run {
+    base: usize,
+    len: usize,
+}
+
+/// Auxiliary function timsorting `arr` without cloning the elements.
+fn timsort_aux<T, F, R>(arr: &mut [T], is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    // The indices are sorted instead of the elements, since the merges need to copy them.
+    let mut perm: Vec<usize> = (0..arr.len()).collect();
+    steps.record_allocation();
+    timsort_indices(&mut perm, &mut |a: &usize, b: &usize| is_less(&arr[*a], &arr[*b]), &mut Permuted { arr, steps });
+
+    apply_permutation(arr, perm, &mut ());
+}
+
+/// Auxiliary function sorting `arr` by `is_less`, finding the runs and merging them while keeping
+/// the invariants of the stack.
+fn timsort_indices<T, F, R>(arr: &mut [T], is_less: &mut F, steps: &mut R)
+    where T: Copy,
+          F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    let length = arr.len();
+    let min_run = min_run_length(length);
+
+    let mut runs: Vec<Run> = vec![];
+    steps.record_allocation();
+    let mut min_gallop = MIN_GALLOP;
+
+    let mut lo = 0;
+    while lo < length {
+        let mut len = count_run_and_make_ascending(arr, lo, length, is_less, steps);
+
+        // Extend short runs to the minimum run length.
+        if len < min_run {
+            let forced = min_run.min(length - lo);
+            binary_insertion_sort(arr, lo, lo + forced, lo + len, is_less, steps);
+            len = forced;
+        }
+
+        steps.record(arr);
+
+        runs.push(Run { base: lo, len });
+        merge_collapse(arr, &mut runs, &mut min_gallop, is_less, steps);
+
+        lo += len;
+    }
+
+    // Merge all the remaining runs.
+    while runs.len() > 1 {
+        let mut n = runs.len() - 2;
+        if n > 0 && runs[n-1].len < runs[n+1].len {
+            n -= 1;
+        }
+        merge_at(arr, &mut runs, n, &mut min_gallop, is_less, steps);
+    }
+}
+
+/// Returns the minimum length of a run, chosen such that the number of runs is a power of two
+/// or slightly less than one, which keeps the merges balanced.
+fn min_run_length(mut n: usize) -> usize {
+    let mut r = 0;
+    while n >= MIN_MERGE {
+        r |= n & 1;
+        n >>= 1;
+    }
+    n + r
+}
+
+/// Returns the length of the run starting at `lo`, reversing it if it is strictly descending.
+///
+/// Only strictly descending runs are reversed, since reversing equal elements would break the
+/// stability of the sort.
+fn count_run_and_make_ascending<T, F, R>(arr: &mut [T], lo: usize, hi: usize, is_less: &mut F, steps: &mut R) -> usize
+    where T: Copy,
+          F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    let mut run_hi = lo + 1;
+    if run_hi == hi {
+        return 1;
+    }
+
+    if less(arr, run_hi, lo, is_less, steps) {
+        run_hi += 1;
+        while run_hi < hi && less(arr, run_hi, run_hi-1, is_less, steps) {
+            run_hi += 1;
+        }
+        for i in 0..(run_hi - lo) / 2 {
+            arr.swap(lo + i, run_hi - 1 - i);
+            steps.record_event(SortEvent::Swap(lo + i, run_hi - 1 - i));
+        }
+    } else {
+        run_hi += 1;
+        while run_hi < hi {
+            if less(arr, run_hi, run_hi-1, is_less, steps) {
+                break;
+            }
+            run_hi += 1;
+        }
+    }
+
+    return run_hi - lo;
+}
+
+/// Auxiliary function sorting `arr[lo..hi]` of which `arr[lo..start]` is already sorted,
+/// inserting every element at the position found by a binary search.
+fn binary_insertion_sort<T, F, R>(arr: &mut [T], lo: usize, hi: usize, start: usize, is_less: &mut F, steps: &mut R)
+    where T: Copy,
+          F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    for i in start..hi {
+        let pivot = arr[i];
+        steps.record_event(SortEvent::Read(i));
+
+        // Find the position after all elements not greater than the pivot, for stability.
+        let mut left = lo;
+        let mut right = i;
+        while left < right {
+            let mid = left + (right - left) / 2;
+            if less_at(&pivot, i, &arr[mid], mid, is_less, steps) {
+                right = mid;
+            } else {
+                left = mid + 1;
+            }
+        }
+
+        arr.copy_within(left..i, left + 1);
+        arr[left] = pivot;
+        record_writes(arr, left, i + 1, steps);
+    }
+}
+
+/// Merges runs until the lengths on the stack satisfy the invariants
+/// `len[i-2] > len[i-1] + len[i]` and `len[i-1] > len[i]`.
+///
+/// The invariants are checked for the top four runs, as in the corrected version of the
+/// algorithm, since checking only the top three runs does not guarantee them for the whole stack.
+fn merge_collapse<T, F, R>(arr: &mut [T], runs: &mut Vec<Run>, min_gallop: &mut usize, is_less: &mut F, steps: &mut R)
+    where T: Copy,
+          F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    while runs.len() > 1 {
+        let mut n = runs.len() - 2;
+
+        if (n > 0 && runs[n-1].len <= runs[n].len + runs[n+1].len)
+            || (n > 1 && runs[n-2].len <= runs[n-1].len + runs[n].len)
+        {
+            if runs[n-1].len < runs[n+1].len {
+                n -= 1;
+            }
+        } else if runs[n].len > runs[n+1].len {
+            break;
+        }
+
+        merge_at(arr, runs, n, min_gallop, is_less, steps);
+    }
+}
+
+/// Merges the runs at `i` and `i + 1` of the stack.
+fn merge_at<T, F, R>(arr: &mut [T], runs: &mut Vec<Run>, i: usize, min_gallop: &mut usize, is_less: &mut F, steps: &mut R)
+    where T: Copy,
+          F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    let Run { base: mut base1, len: mut len1 } = runs[i];
+    let Run { base: base2, len: mut len2 } = runs[i+1];
+
+    runs[i].len = len1 + len2;
+    runs.remove(i + 1);
+
+    steps.record_event(SortEvent::EnterRange(base1, base2 + len2));
+
+    // Elements of the first run not greater than the first element of
+    // the second run are already in place.
+    let k = gallop_right(&arr[base2], base2, &arr[base1..base1 + len1], base1, 0, is_less, steps);
+    base1 += k;
+    len1 -= k;
+
+    // Likewise for the elements of the second run not smaller than
+    // the last element of the first run.
+    if len1 > 0 {
+        let last = base1 + len1 - 1;
+        len2 = gallop_left(&arr[last], last, &arr[base2..base2 + len2], base2, len2 - 1, is_less, steps);
+    }
+
+    if len1 > 0 && len2 > 0 {
+        // Only the shorter run is copied into temporary storage.
+        if len1 <= len2 {
+            merge_lo(arr, base1, len1, len2, min_gallop, is_less, steps);
+        } else {
+            merge_hi(arr, base1, len1, len2, min_gallop, is_less, steps);
+        }
+    }
+
+    steps.record(arr);
+}
+
+/// Returns the position of the first element of the sorted `run` not smaller than `key`,
+/// galloping from `hint` first.
+///
+/// `key_at` and `run_at` are the positions `key` and `run` were read from, which the comparisons
+/// are recorded with.
+fn gallop_left<T, F, R>(key: &T, key_at: usize, run: &[T], run_at: usize, hint: usize, is_less: &mut F, steps: &mut R) -> usize
+    where T: Copy,
+          F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    let mut before_key = |i: usize| less_at(&run[i], run_at + i, key, key_at, is_less, steps);
+
+    let (mut lo, mut hi);
+    let mut last_ofs = 0;
+    let mut ofs = 1;
+
+    if before_key(hint) {
+        // Gallop to the right until `run[hint + last_ofs] < key <= run[hint + ofs]`.
+        let max_ofs = run.len() - hint;
+        while ofs < max_ofs && before_key(hint + ofs) {
+            last_ofs = ofs;
+            ofs = (ofs << 1) + 1;
+        }
+        ofs = ofs.min(max_ofs);
+
+        lo = hint + last_ofs + 1;
+        hi = hint + ofs;
+    } else {
+        // Gallop to the left until `run[hint - ofs] < key <= run[hint - last_ofs]`.
+        let max_ofs = hint + 1;
+        while ofs < max_ofs {
+            if before_key(hint - ofs) {
+                break;
+            }
+            last_ofs = ofs;
+            ofs = (ofs << 1) + 1;
+        }
+        ofs = ofs.min(max_ofs);
+
+        lo = hint + 1 - ofs;
+        hi = hint - last_ofs;
+    }
+
+    // Binary search the remaining range.
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if before_key(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+
+    return hi;
+}
+
+/// Returns the position of the first element of the sorted `run` greater than `key`,
+/// galloping from `hint` first.
+///
+/// `key_at` and `run_at` are the positions like for `gallop_left`.
+fn gallop_right<T, F, R>(key: &T, key_at: usize, run: &[T], run_at: usize, hint: usize, is_less: &mut F, steps: &mut R) -> usize
+    where T: Copy,
+          F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    let mut after_key = |i: usize| less_at(key, key_at, &run[i], run_at + i, is_less, steps);
+
+    let (mut lo, mut hi);
+    let mut last_ofs = 0;
+    let mut ofs = 1;
+
+    if after_key(hint) {
+        // Gallop to the left until `run[hint - ofs] <= key < run[hint - last_ofs]`.
+        let max_ofs = hint + 1;
+        while ofs < max_ofs && after_key(hint - ofs) {
+            last_ofs = ofs;
+            ofs = (ofs << 1) + 1;
+        }
+        ofs = ofs.min(max_ofs);
+
+        lo = hint + 1 - ofs;
+        hi = hint - last_ofs;
+    } else {
+        // Gallop to the right until `run[hint + last_ofs] <= key < run[hint + ofs]`.
+        let max_ofs = run.len() - hint;
+        while ofs < max_ofs {
+            if after_key(hint + ofs) {
+                break;
+            }
+            last_ofs = ofs;
+            ofs = (ofs << 1) + 1;
+        }
+        ofs = ofs.min(max_ofs);
+
+        lo = hint + last_ofs + 1;
+        hi = hint + ofs;
+    }
+
+    // Binary search the remaining range.
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if after_key(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+
+    return lo;
+}
+
+/// Merges the adjacent runs `arr[base1..base1 + len1]` and `arr[base1 + len1..][..len2]` from the
+/// left, copying the first, shorter run into temporary storage.
+///
+/// The first element of the second run must be smaller than the first element of the first run,
+/// and the last element of the first run must be greater than all elements of the second run.
+fn merge_lo<T, F, R>(arr: &mut [T], base1: usize, mut len1: usize, mut len2: usize, min_gallop: &mut usize, is_less: &mut F, steps: &mut R)
+    where T: Copy,
+          F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    let tmp = arr[base1..base1 + len1].to_vec();
+    steps.record_allocation();
+    record_reads(base1, base1 + len1, steps);
+
+    // `dest + len1 == cursor2` holds throughout, so the unmerged rest
+    // of the second run is always in its final place. `tmp[cursor1]`
+    // was read from `base1 + cursor1`.
+    let mut cursor1 = 0;
+    let mut cursor2 = base1 + len1;
+    let mut dest = base1;
+
+    arr[dest] = arr[cursor2];
+    record_writes(arr, dest, dest + 1, steps);
+    dest += 1;
+    cursor2 += 1;
+    len2 -= 1;
+
+    'outer: while len2 > 0 && len1 > 1 {
+        let mut count1 = 0;
+        let mut count2 = 0;
+
+        // Merge one element at a time until one run wins often enough.
+        loop {
+            if less_at(&arr[cursor2], cursor2, &tmp[cursor1], base1 + cursor1, is_less, steps) {
+                arr[dest] = arr[cursor2];
+                record_writes(arr, dest, dest + 1, steps);
+                dest += 1;
+                cursor2 += 1;
+                len2 -= 1;
+                count2 += 1;
+                count1 = 0;
+                if len2 == 0 {
+                    break 'outer;
+                }
+            } else {
+                arr[dest] = tmp[cursor1];
+                record_writes(arr, dest, dest + 1, steps);
+                dest += 1;
+                cursor1 += 1;
+                len1 -= 1;
+                count1 += 1;
+                count2 = 0;
+                if len1 == 1 {
+                    break 'outer;
+                }
+            }
+
+            if count1.max(count2) >= *min_gallop {
+                break;
+            }
+        }
+
+        // Gallop for as long as it pays off.
+        loop {
+            count1 = gallop_right(&arr[cursor2], cursor2, &tmp[cursor1..cursor1 + len1], base1 + cursor1, 0, is_less, steps);
+            if count1 != 0 {
+                arr[dest..dest + count1].copy_from_slice(&tmp[cursor1..cursor1 + count1]);
+                record_writes(arr, dest, dest + count1, steps);
+                dest += count1;
+                cursor1 += count1;
+                len1 -= count1;
+                if len1 <= 1 {
+                    break 'outer;
+                }
+            }
+
+            arr[dest] = arr[cursor2];
+            record_writes(arr, dest, dest + 1, steps);
+            dest += 1;
+            cursor2 += 1;
+            len2 -= 1;
+            if len2 == 0 {
+                break 'outer;
+            }
+
+            count2 = gallop_left(&tmp[cursor1], base1 + cursor1, &arr[cursor2..cursor2 + len2], cursor2, 0, is_less, steps);
+            if count2 != 0 {
+                arr.copy_within(cursor2..cursor2 + count2, dest);
+                record_writes(arr, dest, dest + count2, steps);
+                dest += count2;
+                cursor2 += count2;
+                len2 -= count2;
+                if len2 == 0 {
+                    break 'outer;
+                }
+            }
+
+            arr[dest] = tmp[cursor1];
+            record_writes(arr, dest, dest + 1, steps);
+            dest += 1;
+            cursor1 += 1;
+            len1 -= 1;
+            if len1 == 1 {
+                break 'outer;
+            }
+
+            *min_gallop = min_gallop.saturating_sub(1);
+            if count1 < MIN_GALLOP && count2 < MIN_GALLOP {
+                break;
+            }
+        }
+
+        // Penalize leaving the galloping mode.
+        *min_gallop += 2;
+    }
+
+    *min_gallop = (*min_gallop).max(1);
+
+    if len2 > 0 && len1 == 1 {
+        // The last element of the first run is greater than the rest of the second run.
+        arr.copy_within(cursor2..cursor2 + len2, dest);
+        arr[dest + len2] = tmp[cursor1];
+        record_writes(arr, dest, dest + len2 + 1, steps);
+    } else {
+        arr[dest..dest + len1].copy_from_slice(&tmp[cursor1..cursor1 + len1]);
+        record_writes(arr, dest, dest + len1, steps);
+    }
+}
+
+/// Merges the adjacent runs `arr[base1..base1 + len1]` and `arr[base1 + len1..][..len2]` from the
+/// right, copying the second, shorter run into temporary storage.
+///
+/// The same preconditions as for `merge_lo` apply.
+fn merge_hi<T, F, R>(arr: &mut [T], base1: usize, mut len1: usize, mut len2: usize, min_gallop: &mut usize, is_less: &mut F, steps: &mut R)
+    where T: Copy,
+          F: FnMut(&T, &T) -> bool,
+          R: Recorder<T>,
+{
+    let base2 = base1 + len1;
+    let tmp = arr[base2..base2 + len2].to_vec();
+    steps.record_allocation();
+    record_reads(base2, base2 + len2, steps);
+
+    // The unmerged elements are `arr[base1..base1 + len1]` and `tmp[..len2]`,
+    // and the next element is written to `arr[base1 + len1 + len2 - 1]`.
+    // `tmp[i]` was read from `base2 + i`.
+    arr[base1 + len1 + len2 - 1] = arr[base1 + len1 - 1];
+    record_writes(arr, base1 + len1 + len2 - 1, base1 + len1 + len2, steps);
+    len1 -= 1;
+
+    'outer: while len1 > 0 && len2 > 1 {
+        let mut count1 = 0;
+        let mut count2 = 0;
+
+        // Merge one element at a time until one run wins often enough.
+        loop {
+            let dest = base1 + len1 + len2 - 1;
+            if less_at(&tmp[len2 - 1], base2 + len2 - 1, &arr[base1 + len1 - 1], base1 + len1 - 1, is_less, steps) {
+                arr[dest] = arr[base1 + len1 - 1];
+                record_writes(arr, dest, dest + 1, steps);
+                len1 -= 1;
+                count1 += 1;
+                count2 = 0;
+                if len1 == 0 {
+                    break 'outer;
+                }
+            } else {
+                arr[dest] = tmp[len2 - 1];
+                record_writes(arr, dest, dest + 1, steps);
+                len2 -= 1;
+                count2 += 1;
+                count1 = 0;
+                if len2 == 1 {
+                    break 'outer;
+                }
+            }
+
+            if count1.max(count2) >= *min_gallop {
+                break;
+            }
+        }
+
+        // Gallop for as long as it pays off.
+        loop {
+            count1 = len1 - gallop_right(&tmp[len2 - 1], base2 + len2 - 1, &arr[base1..base1 + len1], base1, len1 - 1, is_less, steps);
+            if count1 != 0 {
+                let dest = base1 + len1 - count1 + len2;
+                arr.copy_within(base1 + len1 - count1..base1 + len1, dest);
+                record_writes(arr, dest, dest + count1, steps);
+                len1 -= count1;
+                if len1 == 0 {
+                    break 'outer;
+                }
+            }
+
+            arr[base1 + len1 + len2 - 1] = tmp[len2 - 1];
+            record_writes(arr, base1 + len1 + len2 - 1, base1 + len1 + len2, steps);
+            len2 -= 1;
+            if len2 == 1 {
+                break 'outer;
+            }
+
+            let last = base1 + len1 - 1;
+            count2 = len2 - gallop_left(&arr[last], last, &tmp[..len2], base2, len2 - 1, is_less, steps);
+            if count2 != 0 {
+                let dest = base1 + len1 + len2 - count2;
+                arr[dest..dest + count2].copy_from_slice(&tmp[len2 - count2..len2]);
+                record_writes(arr, dest, dest + count2, steps);
+                len2 -= count2;
+                if len2 <= 1 {
+                    break 'outer;
+                }
+            }
+
+            arr[base1 + len1 + len2 - 1] = arr[base1 + len1 - 1];
+            record_writes(arr, base1 + len1 + len2 - 1, base1 + len1 + len2, steps);
+            len1 -= 1;
+            if len1 == 0 {
+                break 'outer;
+            }
+
+            *min_gallop = min_gallop.saturating_sub(1);
+            if count1 < MIN_GALLOP && count2 < MIN_GALLOP {
+                break;
+            }
+        }
+
+        // Penalize leaving the galloping mode.
+        *min_gallop += 2;
+    }
+
+    *min_gallop = (*min_gallop).max(1);
+
+    if len1 > 0 && len2 == 1 {
+        // The first element of the second run is smaller than the rest of the first run.
+        arr.copy_within(base1..base1 + len1, base1 + 1);
+        arr[base1] = tmp[0];
+        record_writes(arr, base1, base1 + len1 + 1, steps);
+    } else {
+        arr[base1..base1 + len2].copy_from_slice(&tmp[..len2]);
+        record_writes(arr, base1, base1 + len2, steps);
+    }
+}
+
+/// Auxiliary function recording a read of every element of `lo..hi`.
+fn record_reads<T, R>(lo: usize, hi: usize, steps: &mut R)
+    where R: Recorder<T>,
+{
+    for i in lo..hi {
+        steps.record_event(SortEvent::Read(i));
+    }
+}
+
+/// Auxiliary function recording a write of every element of `arr[lo..hi]`.
+fn record_writes<T, R>(arr: &[T], lo: usize, hi: usize, steps: &mut R)
+    where R: Recorder<T>,
+{
+    for (i, value) in arr[lo..hi].iter().enumerate() {
+        steps.record_event(SortEvent::Write(lo + i, value));
+    }
+}
@@ -0,0 +1,88 @@
+use super::recorder::{ Recorder, AllocationRecorder };
+
+/// An event of a sort, as the traced variants of the sorts record them.
+///
+/// The indices are the positions in the array at the time of the event. The elements a sort holds
+/// aside, like the run timsort copies into temporary storage to merge it, are referred to by the
+/// position they were read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortEvent<T> {
+    /// The elements at the two indices were compared.
+    Compare(usize, usize),
+    /// The elements at the two indices were swapped.
+    Swap(usize, usize),
+    /// The value was written to the index, replacing the element there.
+    Write(usize, T),
+    /// The element at the index was read on its own, like the non-comparison sorts read the keys
+    /// and the merges copy elements aside.
+    Read(usize),
+    /// The sort started working on the range `lo..hi` on its own, like on a partition.
+    EnterRange(usize, usize),
+    /// The element at the index was chosen as the pivot.
+    Pivot(usize),
+    /// The element at the index reached its final position.
+    MarkSorted(usize),
+}
+
+/// The events of a sort, in the order they happened.
+pub type SortTrace<T> = Vec<SortEvent<T>>;
+
+impl<T> SortEvent<T> {
+    /// Maps the value of a `Write` with `f`, keeping every other event as it is.
+    pub fn map<U, F>(self, f: F) -> SortEvent<U>
+        where F: FnOnce(T) -> U,
+    {
+        return match self {
+            SortEvent::Compare(i, j) => SortEvent::Compare(i, j),
+            SortEvent::Swap(i, j) => SortEvent::Swap(i, j),
+            SortEvent::Write(i, value) => SortEvent::Write(i, f(value)),
+            SortEvent::Read(i) => SortEvent::Read(i),
+            SortEvent::EnterRange(lo, hi) => SortEvent::EnterRange(lo, hi),
+            SortEvent::Pivot(i) => SortEvent::Pivot(i),
+            SortEvent::MarkSorted(i) => SortEvent::MarkSorted(i),
+        };
+    }
+}
+
+/// Rebuilds the steps of a sort from the array it started with and its trace.
+///
+/// Like the stepped variants, it starts with the initial array and records a step for every
+/// change. The steps are finer-grained than theirs though, since there is one for every swap and
+/// every write, where e.g. merge sort records one for a whole merge.
+pub fn replay<T>(initial: &[T], trace: &[SortEvent<T>]) -> Vec<Vec<T>>
+    where T: Clone,
+{
+    let mut arr = initial.to_vec();
+    let mut steps = vec![arr.clone()];
+
+    for event in trace {
+        match event {
+            SortEvent::Swap(i, j) => arr.swap(*i, *j),
+            SortEvent::Write(i, value) => arr[*i] = value.clone(),
+            _ => continue,
+        }
+
+        steps.push(arr.clone());
+    }
+
+    return steps;
+}
+
+impl<T> AllocationRecorder for SortTrace<T> {}
+
+/// Keeps the events alone, since the steps can be rebuilt from them.
+impl<T> Recorder<T> for SortTrace<T>
+    where T: Clone,
+{
+    fn record(&mut self, _arr: &[T]) {}
+
+    fn record_permuted(&mut self, _arr: &[T], _perm: &[usize]) {}
+
+    fn record_event(&mut self, event: SortEvent<&T>) {
+        self.push(event.map(T::clone));
+    }
+
+    fn is_recording(&self) -> bool {
+        return false;
+    }
+}
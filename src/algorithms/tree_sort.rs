@@ -1,8 +1,336 @@
-///// The tree sort algorithm.
-/////
-///// Sorts the given `Vec` and returns the result.
-//pub fn tree_sort<T>(arr: Vec<T>) -> Vec<T>
-//    where T: PartialEq + PartialOrd + Clone + Copy,
-//{
-//    todo!()
-//}
+use super::recorder::{ Recorder, AllocationRecorder, Permuted, apply_permutation, Keyed, less, recurse };
+use super::trace::SortTrace;
+use super::observer::{ SortObserver, Observed };
+use super::stats::Counter;
+use super::limits::Limited;
+
+/// The steps of a tree sort, each being a tuple of the contents of the tree (in order) and the
+/// array.
+pub type TreeSortSteps<T> = Vec<(Vec<T>, Vec<T>)>;
+
+/// How the binary search tree used by tree sort keeps itself balanced.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TreeBalance {
+    /// A plain binary search tree, which degenerates into a list on sorted input.
+    #[default]
+    Unbalanced,
+    /// A self-balancing AVL tree, which keeps the sort at O(n log n) for any input.
+    Avl,
+}
+
+comparison_sort! {
+    /// A trait providing the tree sort method.
+    pub trait TreeSort: tree_sort, "tree sort", " using an unbalanced binary search tree";
+    steps TreeSortSteps<T> = |arr| vec![(vec![], arr.to_vec())];
+    stepped "Each step is a tuple of the tree's contents (in order) and the array. A step is recorded \
+        after every insertion into the tree and after every swap moving the values into place in \
+        the array.";
+    core |arr, is_less, steps| tree_sort_aux(arr, TreeBalance::default(), is_less, steps);
+
+    fn tree_sort_with_balance(balance: TreeBalance): " with a given kind of tree"
+        where { T: PartialEq + PartialOrd, }
+        => |arr, steps| tree_sort_aux(arr, balance, &mut T::lt, steps);
+}
+
+/// Auxiliary function building the tree and moving the values of `arr` into place in order.
+fn tree_sort_aux<T, F, R>(arr: &mut [T], balance: TreeBalance, is_less: &mut F, steps: &mut R)
+    where F: FnMut(&T, &T) -> bool,
+          R: TreeRecorder<T>,
+{
+    // The tree only holds the indices of the values, so they never have to be cloned.
+    let mut tree = Tree::new(arr, balance);
+    steps.record_allocation();
+
+    for i in 0..arr.len() {
+        // The values stay where they are if the sort is stopped, since only those in the tree
+        // would be in order.
+        if steps.is_stopped() {
+            return;
+        }
+
+        tree.insert(i, is_less, steps);
+        steps.record_tree(&tree);
+    }
+
+    // Walking the tree takes a stack besides the order itself.
+    let order = tree.in_order();
+    steps.record_allocation();
+    steps.record_allocation();
+    apply_permutation(arr, order, steps);
+}
+
+/// A trait for the places tree sort records its steps into, which also hold the contents of the
+/// tree.
+trait TreeRecorder<T>: Recorder<T> {
+    /// Records the contents of the tree and the array.
+    fn record_tree(&mut self, tree: &Tree<T>);
+
+    /// Records the contents of the tree and the array as seen through `contents` and `perm`,
+    /// like `Recorder::record_permuted`.
+    fn record_tree_permuted(&mut self, arr: &[T], contents: &[usize], perm: &[usize]);
+}
+
+impl<T> TreeRecorder<T> for () {
+    fn record_tree(&mut self, _tree: &Tree<T>) {}
+
+    fn record_tree_permuted(&mut self, _arr: &[T], _contents: &[usize], _perm: &[usize]) {}
+}
+
+impl<T> TreeRecorder<T> for TreeSortSteps<T>
+    where T: Clone,
+{
+    fn record_tree(&mut self, tree: &Tree<T>) {
+        let contents = tree.in_order().into_iter().map(|i| tree.arr[i].clone()).collect();
+        self.push((contents, tree.arr.to_vec()));
+    }
+
+    fn record_tree_permuted(&mut self, arr: &[T], contents: &[usize], perm: &[usize]) {
+        let values = |perm: &[usize]| perm.iter().map(|&i| arr[i].clone()).collect();
+        self.push((values(contents), values(perm)));
+    }
+}
+
+impl<T> TreeRecorder<T> for SortTrace<T>
+    where T: Clone,
+{
+    fn record_tree(&mut self, _tree: &Tree<T>) {}
+
+    fn record_tree_permuted(&mut self, _arr: &[T], _contents: &[usize], _perm: &[usize]) {}
+}
+
+impl<T> TreeRecorder<T> for Counter {
+    fn record_tree(&mut self, _tree: &Tree<T>) {}
+
+    fn record_tree_permuted(&mut self, _arr: &[T], _contents: &[usize], _perm: &[usize]) {}
+}
+
+impl<T> TreeRecorder<T> for Limited {
+    fn record_tree(&mut self, _tree: &Tree<T>) {}
+
+    fn record_tree_permuted(&mut self, _arr: &[T], _contents: &[usize], _perm: &[usize]) {}
+}
+
+impl<T, O> TreeRecorder<T> for Observed<O>
+    where O: SortObserver<T>,
+{
+    fn record_tree(&mut self, _tree: &Tree<T>) {}
+
+    fn record_tree_permuted(&mut self, _arr: &[T], _contents: &[usize], _perm: &[usize]) {}
+}
+
+/// Records the values the keys belong to.
+impl<T, K, R> TreeRecorder<Keyed<K>> for Permuted<'_, T, R>
+    where R: TreeRecorder<T>,
+{
+    fn record_tree(&mut self, tree: &Tree<Keyed<K>>) {
+        if self.steps.is_recording() {
+            let contents: Vec<usize> = tree.in_order()
+                .into_iter()
+                .map(|i| tree.arr[i].index)
+                .collect();
+            let perm: Vec<usize> = tree.arr.iter().map(|k| k.index).collect();
+            self.steps.record_tree_permuted(self.arr, &contents, &perm);
+        }
+    }
+
+    fn record_tree_permuted(&mut self, keyed: &[Keyed<K>], contents: &[usize], perm: &[usize]) {
+        if self.steps.is_recording() {
+            let contents: Vec<usize> = contents.iter().map(|&i| keyed[i].index).collect();
+            let perm: Vec<usize> = perm.iter().map(|&i| keyed[i].index).collect();
+            self.steps.record_tree_permuted(self.arr, &contents, &perm);
+        }
+    }
+}
+
+impl<T> AllocationRecorder for TreeSortSteps<T> {}
+
+/// The contents of the tree don't change once the values are being moved into place, so the last
+/// recorded contents are kept.
+///
+/// The steps of selection sort are of the same type, so this is their implementation too.
+impl<T> Recorder<T> for TreeSortSteps<T>
+    where T: Clone,
+{
+    fn record(&mut self, arr: &[T]) {
+        let contents = self.last().map(|(contents, _)| contents.clone()).unwrap_or_default();
+        self.push((contents, arr.to_vec()));
+    }
+
+    fn record_permuted(&mut self, arr: &[T], perm: &[usize]) {
+        let contents = self.last().map(|(contents, _)| contents.clone()).unwrap_or_default();
+        self.push((contents, perm.iter().map(|&i| arr[i].clone()).collect()));
+    }
+}
+
+/// A node of the tree, linking to its children by their index in the tree's arena.
+///
+/// The node at index `i` of the arena holds the value at index `i` of the array.
+struct Node {
+    left: Option<usize>,
+    right: Option<usize>,
+    height: usize,
+}
+
+/// An arena-backed binary search tree of the values of an array, optionally kept balanced as an
+/// AVL tree.
+///
+/// Equal values are inserted to the right, which keeps the sort stable.
+struct Tree<'a, T> {
+    arr: &'a [T],
+    nodes: Vec<Node>,
+    root: Option<usize>,
+    balance: TreeBalance,
+}
+
+impl<'a, T> Tree<'a, T> {
+    fn new(arr: &'a [T], balance: TreeBalance) -> Self {
+        return Tree { arr, nodes: Vec::with_capacity(arr.len()), root: None, balance };
+    }
+
+    /// Inserts the next value of the array, which is the one at index `new`.
+    fn insert<F, R>(&mut self, new: usize, is_less: &mut F, steps: &mut R)
+        where F: FnMut(&T, &T) -> bool,
+              R: Recorder<T>,
+    {
+        debug_assert_eq!(new, self.nodes.len());
+        self.nodes.push(Node { left: None, right: None, height: 1 });
+
+        match self.balance {
+            TreeBalance::Unbalanced => self.insert_unbalanced(new, is_less, steps),
+            TreeBalance::Avl => self.root = Some(self.insert_avl(self.root, new, is_less, steps)),
+        }
+    }
+
+    /// Iterative insertion, since an unbalanced tree may be as deep as it is large.
+    fn insert_unbalanced<F, R>(&mut self, new: usize, is_less: &mut F, steps: &mut R)
+        where F: FnMut(&T, &T) -> bool,
+              R: Recorder<T>,
+    {
+        let mut current = match self.root {
+            Some(root) => root,
+            None => {
+                self.root = Some(new);
+                return;
+            }
+        };
+
+        loop {
+            let child = if less(self.arr, new, current, is_less, steps) {
+                &mut self.nodes[current].left
+            } else {
+                &mut self.nodes[current].right
+            };
+
+            match *child {
+                Some(next) => current = next,
+                None => {
+                    *child = Some(new);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Recursive insertion into the subtree rooted at `node`, returning the new root of it.
+    fn insert_avl<F, R>(&mut self, node: Option<usize>, new: usize, is_less: &mut F, steps: &mut R) -> usize
+        where F: FnMut(&T, &T) -> bool,
+              R: Recorder<T>,
+    {
+        let node = match node {
+            Some(node) => node,
+            None => return new,
+        };
+
+        if less(self.arr, new, node, is_less, steps) {
+            let left = recurse(steps, |steps| self.insert_avl(self.nodes[node].left, new, is_less, steps));
+            self.nodes[node].left = Some(left);
+        } else {
+            let right = recurse(steps, |steps| self.insert_avl(self.nodes[node].right, new, is_less, steps));
+            self.nodes[node].right = Some(right);
+        }
+
+        self.update_height(node);
+        return self.rebalance(node);
+    }
+
+    /// Returns the height of the subtree rooted at `node`, which is 0 for an empty one.
+    fn height(&self, node: Option<usize>) -> usize {
+        return node.map_or(0, |n| self.nodes[n].height);
+    }
+
+    /// Returns how much higher the left subtree of `node` is than its right one.
+    fn balance_factor(&self, node: usize) -> isize {
+        return self.height(self.nodes[node].left) as isize - self.height(self.nodes[node].right) as isize;
+    }
+
+    /// Recomputes the height of `node` from the heights of its children.
+    fn update_height(&mut self, node: usize) {
+        let left = self.height(self.nodes[node].left);
+        let right = self.height(self.nodes[node].right);
+        self.nodes[node].height = 1 + left.max(right);
+    }
+
+    /// Rotates the subtree rooted at `node` to the left, returning its new root.
+    fn rotate_left(&mut self, node: usize) -> usize {
+        let pivot = self.nodes[node].right.expect("rotating left requires a right child");
+        self.nodes[node].right = self.nodes[pivot].left;
+        self.nodes[pivot].left = Some(node);
+        self.update_height(node);
+        self.update_height(pivot);
+        return pivot;
+    }
+
+    /// Rotates the subtree rooted at `node` to the right, returning its new root.
+    fn rotate_right(&mut self, node: usize) -> usize {
+        let pivot = self.nodes[node].left.expect("rotating right requires a left child");
+        self.nodes[node].left = self.nodes[pivot].right;
+        self.nodes[pivot].right = Some(node);
+        self.update_height(node);
+        self.update_height(pivot);
+        return pivot;
+    }
+
+    /// Rotates the subtree rooted at `node` back into balance if an insertion left it unbalanced,
+    /// returning its new root.
+    fn rebalance(&mut self, node: usize) -> usize {
+        let factor = self.balance_factor(node);
+
+        if factor > 1 {
+            let left = self.nodes[node].left.expect("a left-heavy node has a left child");
+            if self.balance_factor(left) < 0 {
+                self.nodes[node].left = Some(self.rotate_left(left));
+            }
+            return self.rotate_right(node);
+        }
+
+        if factor < -1 {
+            let right = self.nodes[node].right.expect("a right-heavy node has a right child");
+            if self.balance_factor(right) > 0 {
+                self.nodes[node].right = Some(self.rotate_right(right));
+            }
+            return self.rotate_left(node);
+        }
+
+        return node;
+    }
+
+    /// Iterative in-order traversal of the tree, returning the indices of the values.
+    fn in_order(&self) -> Vec<usize> {
+        let mut values = Vec::with_capacity(self.nodes.len());
+        let mut stack = vec![];
+        let mut current = self.root;
+
+        while current.is_some() || !stack.is_empty() {
+            while let Some(node) = current {
+                stack.push(node);
+                current = self.nodes[node].left;
+            }
+
+            let node = stack.pop().expect("the stack is not empty");
+            values.push(node);
+            current = self.nodes[node].right;
+        }
+
+        return values;
+    }
+}
@@ -0,0 +1,778 @@
+//! The macros generating the variants of every sort.
+//!
+//! Every algorithm file gives `comparison_sort!` or `radix_sort!` the trait of the sort, its core
+//! and the rows of its own, which add the rows every comparison or radix sort has and hand them all
+//! to `sort_variants!`. Their input reads:
+//!
+//! ```text
+//! comparison_sort! {
+//!     /// The docs of the trait.
+//!     pub trait Trait: base, "title" [, "plain how"];
+//!     [tail "how it ends" [, "how the limited variants end"];]
+//!     [steps StepsType = |arr| first steps;]
+//!     [stepped "what a step is";]
+//!     core |arr, is_less, steps| sorting arr with is_less into steps;
+//!
+//!     [/// The docs of the row.]
+//!     fn name[<G, ..>](param: Type, ..): "what"
+//!         where { bounds of the plain variant, }
+//!         [panics "when every variant of the row panics"]
+//!         [steps { <G, ..> (param: Type, ..) where { bounds, } => (arguments) }]
+//!         [check |arr| { checking arr before anything else }]
+//!         => |arr, steps| sorting arr into steps;
+//!     ..
+//! }
+//! ```
+//!
+//! `radix_sort!` reads the same, but for having no `tail` and `steps` and a core without
+//! `is_less`.
+//!
+//! - The title and the phrases, like `"what"`, make up the docs of every variant, e.g. "The comb
+//!   sort algorithm with a given shrink factor.", with the row's own docs below them.
+//! - `base` names the plain row, whose docs end with `"plain how"`, e.g. " using a radix of 256".
+//! - `steps StepsType` replaces the `Vec<Vec<T>>` the stepped variants record by default, starting
+//!   with the first steps built from the slice, and `stepped` says what a step of it is.
+//! - Every row has an own `fn`: the plain variant with its parameters and bounds, which every other
+//!   variant of the row adds its own to, and the body every one of them runs with its own recorder
+//!   as `steps`.
+//! - The lazily stepped variant of a row sends its parameters to the thread sorting a copy, so it
+//!   adds `T: Clone + Send + 'static` to the bounds. A row whose parameters can't be sent as they
+//!   are, like a `&mut` generator, gives the parameters, the bounds and the arguments of its
+//!   `_observed` variant after `steps` instead.
+//! - `check` runs in every variant before anything else, even on slices too short to be sorted, so
+//!   it is where the parameters are asserted, like the shrink factor of comb sort.
+//!
+//! `sort_variants!` takes the same trait header, but for the `base` and `core`, and then every row
+//! in braces, with the phrase describing the function, e.g. " with a comparator", and the one
+//! describing how it sorts, e.g. " with `compare`", in brackets, either of which may be empty. Its
+//! rows can also give `fails ErrorType` after the `check`, which makes every variant return a
+//! `Result` and lets the `check` return that error with `?`, like the `_with_nans` rows do.
+
+/// Generates the trait of a sort, its implementation for slices and its free functions from the
+/// rows of its variants, like `_by` or `_with_nans`, each of which comes plain, timed, stepped,
+/// stepped _and_ timed, traced, observed, counted, limited and lazily stepped.
+///
+/// A row gives the name, parameters and bounds of its plain variant, the phrases describing it,
+/// when every variant of it `panics`, and the core sorting `arr` into the recorder `steps`, which
+/// every variant runs with its own recorder. Its lazily stepped variant takes the same parameters
+/// unless it gives others after `steps`, it can `check` the slice before anything else, and it
+/// `fails` with the given error type if the check can.
+///
+/// The stepped variants record `Vec<Vec<T>>` starting with the slice itself, unless the sort gives
+/// another type of steps and their start after `steps`, and what a step is after `stepped`. The
+/// descriptions end with `tail`, or with the second one given for the limited variants.
+macro_rules! sort_variants {
+    (@trait [$($attr:tt)*] $Trait:ident $title:tt [] $steps:tt $($row:tt)*) => {
+        sort_variants!(@trait [$($attr)*] $Trait $title ["", ""] $steps $($row)*);
+    };
+    (@trait [$($attr:tt)*] $Trait:ident $title:tt [$tail:literal] $steps:tt $($row:tt)*) => {
+        sort_variants!(@trait [$($attr)*] $Trait $title [$tail, $tail] $steps $($row)*);
+    };
+    (@trait [$($attr:tt)*] $Trait:ident $title:tt $tail:tt [] $($row:tt)*) => {
+        sort_variants!(@trait [$($attr)*] $Trait $title $tail [Vec<Vec<T>> = |arr| vec![arr.to_vec()]] $($row)*);
+    };
+    (@trait [$($attr:tt)*] $Trait:ident [$title:literal] [$tail:literal, $ltail:literal] [$Steps:ty = |$iarr:ident| $init:expr] $stepped:tt $($row:tt)*) => {
+        $($attr)*
+        pub trait $Trait<T> {
+            $( sort_variants!(@decl [$title, $tail, $ltail, $stepped, $Steps] $row); )*
+        }
+
+        #[doc = concat!("The trait implementation of the ", $title, " algorithm.")]
+        impl<T> $Trait<T> for [T] {
+            $( sort_variants!(@impl [$Steps, |$iarr| $init] $row); )*
+        }
+
+        $( sort_variants!(@fn [$title, $tail, $ltail, $stepped, $Steps, |$iarr| $init] $row); )*
+    };
+
+    (@ret [] $ty:ty) => { $ty };
+    (@ret [$err:ty] $ty:ty) => { Result<$ty, $err> };
+
+    (@ok [] $value:expr) => { $value };
+    (@ok [$err:ty] $value:expr) => { Ok($value) };
+
+    (@decl [$title:literal, $tail:literal, $ltail:literal, [$($stepped:literal)?], $Steps:ty] {
+        $(#[$note:meta])*
+        fn $name:ident $(<$($g:ident),*>)? ($($p:ident: $pt:ty),*): [$($what:literal)?] [$($how:literal)?]
+            where { $($bound:tt)* }
+            $(panics $panics:literal)?
+            $(steps $lazy:tt)?
+            $(check |$c:ident| $check:block)?
+            $(fails $err:ty)?
+            => |$a:ident, $s:ident| $body:expr
+    }) => { paste::paste! {
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? ".")]
+        #[doc = ""]
+        #[doc = concat!("Sorts the slice it is called on", $($how,)? $tail, ".")]
+        #[doc = ""]
+        $(#[$note])*
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        fn $name<$($($g),*)?>(&mut self, $($p: $pt),*) -> sort_variants!(@ret [$($err)?] ())
+            where $($bound)*;
+
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? " but timed.")]
+        #[doc = ""]
+        #[doc = concat!("Sorts the slice it is called on", $($how,)? " and returns the `Duration` of the process", $tail, ".")]
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        fn [<$name _timed>]<$($($g),*)?>(&mut self, $($p: $pt),*)
+            -> sort_variants!(@ret [$($err)?] ::std::time::Duration)
+            where $($bound)*;
+
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? " but stepped.")]
+        #[doc = ""]
+        #[doc = concat!("Sorts the slice it is called on", $($how,)? " and returns a `Vec` containing each step of the process", $tail, ".")]
+        $(#[doc = ""] #[doc = $stepped])?
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        fn [<$name _stepped>]<$($($g),*)?>(&mut self, $($p: $pt),*) -> sort_variants!(@ret [$($err)?] $Steps)
+            where $($bound)* T: Clone;
+
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? " but stepped _and_ timed.")]
+        #[doc = ""]
+        #[doc = concat!("Sorts the slice it is called on", $($how,)? " and returns a `Vec` containing each step of the process, including the `Duration` of the entire process", $tail, ".")]
+        $(#[doc = ""] #[doc = $stepped])?
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        fn [<$name _stepped_and_timed>]<$($($g),*)?>(&mut self, $($p: $pt),*)
+            -> sort_variants!(@ret [$($err)?] ($Steps, ::std::time::Duration))
+            where $($bound)* T: Clone;
+
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? " but traced.")]
+        #[doc = ""]
+        #[doc = concat!("Sorts the slice it is called on", $($how,)? " and returns a `Vec` containing every event of the process", $tail, ".")]
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        fn [<$name _traced>]<$($($g),*)?>(&mut self, $($p: $pt),*)
+            -> sort_variants!(@ret [$($err)?] $crate::algorithms::trace::SortTrace<T>)
+            where $($bound)* T: Clone;
+
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? " but observed.")]
+        #[doc = ""]
+        #[doc = concat!("Sorts the slice it is called on", $($how,)? " while notifying `observer` of every event of the process, which may stop it early", $tail, ".")]
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        fn [<$name _observed>]<$($($g,)*)? O>(&mut self, $($p: $pt,)* observer: O)
+            -> sort_variants!(@ret [$($err)?] ::std::ops::ControlFlow<()>)
+            where $($bound)* O: $crate::algorithms::observer::SortObserver<T>;
+
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? " but counted.")]
+        #[doc = ""]
+        #[doc = concat!("Sorts the slice it is called on", $($how,)? " and returns the `SortStats` of the process", $tail, ".")]
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        fn [<$name _counted>]<$($($g),*)?>(&mut self, $($p: $pt),*)
+            -> sort_variants!(@ret [$($err)?] $crate::algorithms::stats::SortStats)
+            where $($bound)*;
+
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? " but limited.")]
+        #[doc = ""]
+        #[doc = concat!("Sorts the slice it is called on", $($how,)? " unless the process exceeds `limits`, which aborts it", $ltail, ".")]
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        fn [<$name _limited>]<$($($g),*)?>(&mut self, $($p: $pt,)* limits: $crate::algorithms::limits::SortLimits)
+            -> sort_variants!(@ret [$($err)?] Result<(), $crate::algorithms::limits::SortAborted<T>>)
+            where $($bound)* T: Clone;
+    }};
+
+    (@impl [$Steps:ty, |$iarr:ident| $init:expr] {
+        $(#[$note:meta])*
+        fn $name:ident $(<$($g:ident),*>)? ($($p:ident: $pt:ty),*): [$($what:literal)?] [$($how:literal)?]
+            where { $($bound:tt)* }
+            $(panics $panics:literal)?
+            $(steps $lazy:tt)?
+            $(check |$c:ident| $check:block)?
+            $(fails $err:ty)?
+            => |$a:ident, $s:ident| $body:expr
+    }) => { paste::paste! {
+        #[allow(unused_mut)]
+        fn $name<$($($g),*)?>(&mut self, $(mut $p: $pt),*) -> sort_variants!(@ret [$($err)?] ())
+            where $($bound)*
+        {
+            $({ let $c: &[T] = &self[..]; $check })?
+
+            if self.len() > 1 {
+                let $a: &mut [T] = &mut *self;
+                let $s = &mut ();
+                $body;
+            }
+
+            return sort_variants!(@ok [$($err)?] ());
+        }
+
+        #[allow(unused_mut)]
+        fn [<$name _timed>]<$($($g),*)?>(&mut self, $(mut $p: $pt),*)
+            -> sort_variants!(@ret [$($err)?] ::std::time::Duration)
+            where $($bound)*
+        {
+            let time = ::std::time::Instant::now();
+
+            $({ let $c: &[T] = &self[..]; $check })?
+
+            if self.len() > 1 {
+                let $a: &mut [T] = &mut *self;
+                let $s = &mut ();
+                $body;
+            }
+
+            return sort_variants!(@ok [$($err)?] time.elapsed());
+        }
+
+        #[allow(unused_mut)]
+        fn [<$name _stepped>]<$($($g),*)?>(&mut self, $(mut $p: $pt),*) -> sort_variants!(@ret [$($err)?] $Steps)
+            where $($bound)* T: Clone,
+        {
+            $({ let $c: &[T] = &self[..]; $check })?
+
+            let mut recorder: $Steps = { let $iarr: &[T] = &self[..]; $init };
+
+            if self.len() > 1 {
+                let $a: &mut [T] = &mut *self;
+                let $s = &mut recorder;
+                $body;
+            }
+
+            return sort_variants!(@ok [$($err)?] recorder);
+        }
+
+        #[allow(unused_mut)]
+        fn [<$name _stepped_and_timed>]<$($($g),*)?>(&mut self, $(mut $p: $pt),*)
+            -> sort_variants!(@ret [$($err)?] ($Steps, ::std::time::Duration))
+            where $($bound)* T: Clone,
+        {
+            let time = ::std::time::Instant::now();
+
+            $({ let $c: &[T] = &self[..]; $check })?
+
+            let mut recorder: $Steps = { let $iarr: &[T] = &self[..]; $init };
+
+            if self.len() > 1 {
+                let $a: &mut [T] = &mut *self;
+                let $s = &mut recorder;
+                $body;
+            }
+
+            return sort_variants!(@ok [$($err)?] (recorder, time.elapsed()));
+        }
+
+        #[allow(unused_mut)]
+        fn [<$name _traced>]<$($($g),*)?>(&mut self, $(mut $p: $pt),*)
+            -> sort_variants!(@ret [$($err)?] $crate::algorithms::trace::SortTrace<T>)
+            where $($bound)* T: Clone,
+        {
+            $({ let $c: &[T] = &self[..]; $check })?
+
+            let mut recorder: $crate::algorithms::trace::SortTrace<T> = vec![];
+
+            if self.len() > 1 {
+                let $a: &mut [T] = &mut *self;
+                let $s = &mut recorder;
+                $body;
+            }
+
+            return sort_variants!(@ok [$($err)?] recorder);
+        }
+
+        #[allow(unused_mut)]
+        fn [<$name _observed>]<$($($g,)*)? O>(&mut self, $(mut $p: $pt,)* observer: O)
+            -> sort_variants!(@ret [$($err)?] ::std::ops::ControlFlow<()>)
+            where $($bound)* O: $crate::algorithms::observer::SortObserver<T>,
+        {
+            $({ let $c: &[T] = &self[..]; $check })?
+
+            let mut recorder = $crate::algorithms::observer::Observed::new(observer);
+
+            if self.len() > 1 {
+                let $a: &mut [T] = &mut *self;
+                let $s = &mut recorder;
+                $body;
+            }
+
+            return sort_variants!(@ok [$($err)?] recorder.finish());
+        }
+
+        #[allow(unused_mut)]
+        fn [<$name _counted>]<$($($g),*)?>(&mut self, $(mut $p: $pt),*)
+            -> sort_variants!(@ret [$($err)?] $crate::algorithms::stats::SortStats)
+            where $($bound)*
+        {
+            $({ let $c: &[T] = &self[..]; $check })?
+
+            let mut recorder = $crate::algorithms::stats::Counter::default();
+
+            if self.len() > 1 {
+                let $a: &mut [T] = &mut *self;
+                let $s = &mut recorder;
+                $body;
+            }
+
+            return sort_variants!(@ok [$($err)?] recorder.finish());
+        }
+
+        #[allow(unused_mut)]
+        fn [<$name _limited>]<$($($g),*)?>(&mut self, $(mut $p: $pt,)* limits: $crate::algorithms::limits::SortLimits)
+            -> sort_variants!(@ret [$($err)?] Result<(), $crate::algorithms::limits::SortAborted<T>>)
+            where $($bound)* T: Clone,
+        {
+            $({ let $c: &[T] = &self[..]; $check })?
+
+            let mut recorder = $crate::algorithms::limits::Limited::new(limits);
+
+            if self.len() > 1 {
+                let $a: &mut [T] = &mut *self;
+                let $s = &mut recorder;
+                $body;
+            }
+
+            return sort_variants!(@ok [$($err)?] recorder.finish(self));
+        }
+    }};
+
+    (@fn [$title:literal, $tail:literal, $ltail:literal, [$($stepped:literal)?], $Steps:ty, |$iarr:ident| $init:expr] {
+        $(#[$note:meta])*
+        fn $name:ident $(<$($g:ident),*>)? ($($p:ident: $pt:ty),*): [$($what:literal)?] [$($how:literal)?]
+            where { $($bound:tt)* }
+            $(panics $panics:literal)?
+            $(steps $lazy:tt)?
+            $(check |$c:ident| $check:block)?
+            $(fails $err:ty)?
+            => |$a:ident, $s:ident| $body:expr
+    }) => { paste::paste! {
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? ".")]
+        #[doc = ""]
+        #[doc = concat!("Sorts the given `Vec`", $($how,)? " and returns the result", $tail, ".")]
+        #[doc = ""]
+        $(#[$note])*
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        #[allow(unused_mut)]
+        pub fn $name<T, $($($g),*)?>(mut arr: Vec<T>, $(mut $p: $pt),*) -> sort_variants!(@ret [$($err)?] Vec<T>)
+            where $($bound)*
+        {
+            $({ let $c: &[T] = &arr[..]; $check })?
+
+            if arr.len() > 1 {
+                let $a: &mut [T] = &mut arr[..];
+                let $s = &mut ();
+                $body;
+            }
+
+            return sort_variants!(@ok [$($err)?] arr);
+        }
+
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? " but timed.")]
+        #[doc = ""]
+        #[doc = concat!("Sorts the given `Vec`", $($how,)? " and returns the result and the `Duration` of the process", $tail, ".")]
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        #[allow(unused_mut)]
+        pub fn [<$name _timed>]<T, $($($g),*)?>(mut arr: Vec<T>, $(mut $p: $pt),*)
+            -> sort_variants!(@ret [$($err)?] (Vec<T>, ::std::time::Duration))
+            where $($bound)*
+        {
+            let time = ::std::time::Instant::now();
+
+            $({ let $c: &[T] = &arr[..]; $check })?
+
+            if arr.len() > 1 {
+                let $a: &mut [T] = &mut arr[..];
+                let $s = &mut ();
+                $body;
+            }
+
+            return sort_variants!(@ok [$($err)?] (arr, time.elapsed()));
+        }
+
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? " but stepped.")]
+        #[doc = ""]
+        #[doc = concat!("Sorts the given `Vec`", $($how,)? " and returns the result and a `Vec` containing each step of the process", $tail, ".")]
+        $(#[doc = ""] #[doc = $stepped])?
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        #[allow(unused_mut)]
+        pub fn [<$name _stepped>]<T, $($($g),*)?>(mut arr: Vec<T>, $(mut $p: $pt),*)
+            -> sort_variants!(@ret [$($err)?] (Vec<T>, $Steps))
+            where $($bound)* T: Clone,
+        {
+            $({ let $c: &[T] = &arr[..]; $check })?
+
+            let mut recorder: $Steps = { let $iarr: &[T] = &arr[..]; $init };
+
+            if arr.len() > 1 {
+                let $a: &mut [T] = &mut arr[..];
+                let $s = &mut recorder;
+                $body;
+            }
+
+            return sort_variants!(@ok [$($err)?] (arr, recorder));
+        }
+
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? " but stepped _and_ timed.")]
+        #[doc = ""]
+        #[doc = concat!("Sorts the given `Vec`", $($how,)? " and returns the result and a `Vec` containing each step of the process, including the `Duration` of the entire process", $tail, ".")]
+        $(#[doc = ""] #[doc = $stepped])?
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        #[allow(unused_mut)]
+        pub fn [<$name _stepped_and_timed>]<T, $($($g),*)?>(mut arr: Vec<T>, $(mut $p: $pt),*)
+            -> sort_variants!(@ret [$($err)?] (Vec<T>, $Steps, ::std::time::Duration))
+            where $($bound)* T: Clone,
+        {
+            let time = ::std::time::Instant::now();
+
+            $({ let $c: &[T] = &arr[..]; $check })?
+
+            let mut recorder: $Steps = { let $iarr: &[T] = &arr[..]; $init };
+
+            if arr.len() > 1 {
+                let $a: &mut [T] = &mut arr[..];
+                let $s = &mut recorder;
+                $body;
+            }
+
+            return sort_variants!(@ok [$($err)?] (arr, recorder, time.elapsed()));
+        }
+
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? " but traced.")]
+        #[doc = ""]
+        #[doc = concat!("Sorts the given `Vec`", $($how,)? " and returns the result and a `Vec` containing every event of the process", $tail, ".")]
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        #[allow(unused_mut)]
+        pub fn [<$name _traced>]<T, $($($g),*)?>(mut arr: Vec<T>, $(mut $p: $pt),*)
+            -> sort_variants!(@ret [$($err)?] (Vec<T>, $crate::algorithms::trace::SortTrace<T>))
+            where $($bound)* T: Clone,
+        {
+            $({ let $c: &[T] = &arr[..]; $check })?
+
+            let mut recorder: $crate::algorithms::trace::SortTrace<T> = vec![];
+
+            if arr.len() > 1 {
+                let $a: &mut [T] = &mut arr[..];
+                let $s = &mut recorder;
+                $body;
+            }
+
+            return sort_variants!(@ok [$($err)?] (arr, recorder));
+        }
+
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? " but observed.")]
+        #[doc = ""]
+        #[doc = concat!("Sorts the given `Vec`", $($how,)? " while notifying `observer` of every event of the process, which may stop it early, and returns the result", $tail, ".")]
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        #[allow(unused_mut)]
+        pub fn [<$name _observed>]<T, $($($g,)*)? O>(mut arr: Vec<T>, $(mut $p: $pt,)* observer: O)
+            -> sort_variants!(@ret [$($err)?] (Vec<T>, ::std::ops::ControlFlow<()>))
+            where $($bound)* O: $crate::algorithms::observer::SortObserver<T>,
+        {
+            $({ let $c: &[T] = &arr[..]; $check })?
+
+            let mut recorder = $crate::algorithms::observer::Observed::new(observer);
+
+            if arr.len() > 1 {
+                let $a: &mut [T] = &mut arr[..];
+                let $s = &mut recorder;
+                $body;
+            }
+
+            return sort_variants!(@ok [$($err)?] (arr, recorder.finish()));
+        }
+
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? " but counted.")]
+        #[doc = ""]
+        #[doc = concat!("Sorts the given `Vec`", $($how,)? " and returns the result and the `SortStats` of the process", $tail, ".")]
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        #[allow(unused_mut)]
+        pub fn [<$name _counted>]<T, $($($g),*)?>(mut arr: Vec<T>, $(mut $p: $pt),*)
+            -> sort_variants!(@ret [$($err)?] (Vec<T>, $crate::algorithms::stats::SortStats))
+            where $($bound)*
+        {
+            $({ let $c: &[T] = &arr[..]; $check })?
+
+            let mut recorder = $crate::algorithms::stats::Counter::default();
+
+            if arr.len() > 1 {
+                let $a: &mut [T] = &mut arr[..];
+                let $s = &mut recorder;
+                $body;
+            }
+
+            return sort_variants!(@ok [$($err)?] (arr, recorder.finish()));
+        }
+
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? " but limited.")]
+        #[doc = ""]
+        #[doc = concat!("Sorts the given `Vec`", $($how,)? " and returns the result unless the process exceeds `limits`, which aborts it", $ltail, ".")]
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        #[allow(unused_mut)]
+        pub fn [<$name _limited>]<T, $($($g),*)?>(mut arr: Vec<T>, $(mut $p: $pt,)* limits: $crate::algorithms::limits::SortLimits)
+            -> sort_variants!(@ret [$($err)?] Result<Vec<T>, $crate::algorithms::limits::SortAborted<T>>)
+            where $($bound)*
+        {
+            $({ let $c: &[T] = &arr[..]; $check })?
+
+            let mut recorder = $crate::algorithms::limits::Limited::new(limits);
+
+            if arr.len() > 1 {
+                let $a: &mut [T] = &mut arr[..];
+                let $s = &mut recorder;
+                $body;
+            }
+
+            return sort_variants!(@ok [$($err)?] recorder.finish_vec(arr));
+        }
+
+        sort_variants!(@steps [$title, $tail, [$($what)?], [$($how)?], [$($stepped)?], [$($panics)?]] [$(|$c| $check)?] [$($err)?] $name
+            [$($lazy)?] <$($($g),*)?> ($($p: $pt),*) where { $($bound)* });
+    }};
+
+    // The lazily stepped variant takes the same parameters as the others by default, which then
+    // have to be sent to the thread sorting the copy.
+    (@steps $docs:tt $check:tt $err:tt $name:ident [] <$($g:ident),*> ($($p:ident: $pt:ty),*) where { $($bound:tt)* }) => {
+        sort_variants!(@steps $docs $check $err $name [{
+            <$($g),*> ($($p: $pt),*) where { $($bound)* T: Clone + Send + 'static, } => ($($p),*)
+        }]);
+    };
+    (@steps [$title:literal, $tail:literal, [$($what:literal)?], [$($how:literal)?], [$($stepped:literal)?], [$($panics:literal)?]] [$(|$c:ident| $check:block)?] [$($err:ty)?] $name:ident [{
+        <$($g:ident),*> ($($p:ident: $pt:ty),*) where { $($bound:tt)* } => ($($arg:expr),*)
+    }] $($default:tt)*) => { paste::paste! {
+        #[doc = concat!("The ", $title, " algorithm", $($what,)? " but lazily stepped.")]
+        #[doc = ""]
+        #[doc = concat!("Returns an iterator over the steps of sorting the given `Vec`", $($how,)? ", which only sorts as far as it is advanced", $tail, ".")]
+        #[doc = ""]
+        #[doc = "The sort runs on a thread of its own, on a copy of the `Vec`, and every step clones the whole"]
+        #[doc = "array, so it takes `O(n)` more per step than the observed variant does. See `Steps`."]
+        $(#[doc = ""] #[doc = $stepped])?
+        $(#[doc = ""] #[doc = "# Panics"] #[doc = ""] #[doc = $panics])?
+        #[allow(unused_mut)]
+        pub fn [<$name _steps>]<T, $($g),*>(arr: Vec<T>, $(mut $p: $pt),*)
+            -> sort_variants!(@ret [$($err)?] $crate::algorithms::steps::Steps<T>)
+            where $($bound)*
+        {
+            $({ let $c: &[T] = &arr[..]; $check })?
+
+            let steps = $crate::algorithms::steps::Steps::new(arr, move |arr, observer| arr.[<$name _observed>]($($arg,)* observer));
+
+            return sort_variants!(@ok [$($err)?] steps);
+        }
+    }};
+
+    (
+        $(#[$attr:meta])*
+        pub trait $Trait:ident: $title:literal;
+        $(tail $tail:literal $(, $ltail:literal)?;)?
+        $(steps $Steps:ty = |$iarr:ident| $init:expr;)?
+        $(stepped $stepped:literal;)?
+        $({ $($row:tt)* })*
+    ) => {
+        sort_variants!(@trait [$(#[$attr])*] $Trait [$title] [$($tail $(, $ltail)?)?] [$($Steps = |$iarr| $init)?] [$($stepped)?] $({ $($row)* })*);
+    };
+}
+
+/// Generates the variants of a comparison sort, from the core sorting `arr` with `is_less` into
+/// the recorder `steps`.
+///
+/// Next to the plain row, whose description ends with `plain` if given, every comparison sort has
+/// `_by`, `_by_key`, `_by_cached_key`, `_with_nans` and `_total` rows, after the rows of its own
+/// it gives with `fn`.
+macro_rules! comparison_sort {
+    (
+        $(#[$attr:meta])*
+        pub trait $Trait:ident: $base:ident, $title:literal $(, $plain:literal)?;
+        $(tail $tail:literal $(, $ltail:literal)?;)?
+        $(steps $Steps:ty = |$iarr:ident| $init:expr;)?
+        $(stepped $stepped:literal;)?
+        core |$arr:ident, $is_less:ident, $steps:ident| $core:expr;
+        $(
+            $(#[$note:meta])*
+            fn $name:ident $(<$($g:ident),*>)? ($($p:ident: $pt:ty),*): $what:literal
+                where { $($bound:tt)* }
+                $(panics $panics:literal)?
+                $(steps $lazy:tt)?
+                $(check |$c:ident| $check:block)?
+                => |$a:ident, $s:ident| $body:expr;
+        )*
+    ) => { paste::paste! { sort_variants! {
+        $(#[$attr])*
+        pub trait $Trait: $title;
+        $(tail $tail $(, $ltail)?;)?
+        $(steps $Steps = |$iarr| $init;)?
+        $(stepped $stepped;)?
+
+        {
+            fn $base(): [] [$($plain)?]
+                where { T: PartialEq + PartialOrd, }
+                => |arr, steps| {
+                    let $arr = arr;
+                    let $is_less = &mut T::lt;
+                    let $steps = steps;
+                    $core
+                }
+        }
+
+        $({
+            $(#[$note])*
+            fn $name $(<$($g),*>)? ($($p: $pt),*): [$what] []
+                where { $($bound)* }
+                $(panics $panics)?
+                $(steps $lazy)?
+                $(check |$c| $check)?
+                => |$a, $s| $body
+        })*
+
+        {
+            fn [<$base _by>]<F>(compare: F): [" with a comparator"] [" with `compare`"]
+                where { F: FnMut(&T, &T) -> ::std::cmp::Ordering, }
+                steps {
+                    <F> (compare: F) where {
+                        T: Clone + Send + 'static,
+                        F: FnMut(&T, &T) -> ::std::cmp::Ordering + Send + 'static,
+                    } => (compare)
+                }
+                => |arr, steps| {
+                    let $arr = arr;
+                    let $is_less = &mut |a: &T, b: &T| compare(a, b) == ::std::cmp::Ordering::Less;
+                    let $steps = steps;
+                    $core
+                }
+        }
+
+        {
+            fn [<$base _by_key>]<K, F>(key: F): [" with a key extraction function"] [" by the keys `key` returns"]
+                where { F: FnMut(&T) -> K, K: PartialEq + PartialOrd, }
+                steps {
+                    <K, F> (key: F) where {
+                        T: Clone + Send + 'static,
+                        F: FnMut(&T) -> K + Send + 'static,
+                        K: PartialEq + PartialOrd,
+                    } => (key)
+                }
+                => |arr, steps| {
+                    let $arr = arr;
+                    let $is_less = &mut |a: &T, b: &T| key(a) < key(b);
+                    let $steps = steps;
+                    $core
+                }
+        }
+
+        {
+            #[doc = concat!("Every key is computed only once, rather than for every comparison, which makes it faster than `", stringify!([<$base _by_key>]), "` for expensive key functions.")]
+            fn [<$base _by_cached_key>]<K, F>(key: F): [" with a cached key extraction function"] [" by the keys `key` returns"]
+                where { F: FnMut(&T) -> K, K: PartialEq + PartialOrd, }
+                steps {
+                    <K, F> (key: F) where {
+                        T: Clone + Send + 'static,
+                        F: FnMut(&T) -> K + Send + 'static,
+                        K: PartialEq + PartialOrd,
+                    } => (key)
+                }
+                => |arr, steps| $crate::algorithms::recorder::sort_keyed(arr, &mut key, steps, |keyed, steps| {
+                    let $arr = keyed;
+                    let $is_less = &mut $crate::algorithms::recorder::Keyed::is_less;
+                    let $steps = steps;
+                    $core;
+                })
+        }
+
+        {
+            /// The elements which are not comparable even to themselves, like `NaN`, go first or
+            /// last as `policy` says, or, if it does not allow them, make it return a `NanError`
+            /// without sorting anything.
+            fn [<$base _with_nans>](policy: $crate::algorithms::nan_policy::NanPolicy): [" with a `NaN` policy"] []
+                where { T: PartialEq + PartialOrd, }
+                check |arr| { policy.check(arr)?; }
+                fails $crate::algorithms::nan_policy::NanError
+                => |arr, steps| {
+                    let $arr = arr;
+                    let $is_less = &mut |a: &T, b: &T| policy.is_less(a, b);
+                    let $steps = steps;
+                    $core
+                }
+        }
+
+        {
+            /// Unlike the partial order, it orders every float, `NaN` included, like `total_cmp`
+            /// does.
+            fn [<$base _total>](): [" with the total order"] [" by the total order of its elements"]
+                where { T: $crate::algorithms::nan_policy::TotalOrder, }
+                => |arr, steps| {
+                    let $arr = arr;
+                    let $is_less = &mut |a: &T, b: &T| {
+                        $crate::algorithms::nan_policy::TotalOrder::total_cmp(a, b) == ::std::cmp::Ordering::Less
+                    };
+                    let $steps = steps;
+                    $core
+                }
+        }
+    }}};
+}
+
+/// Generates the variants of a non-comparison sort of `RadixKey`s, from the core sorting `arr`
+/// into the recorder `steps`.
+///
+/// Next to the plain row, whose description ends with `plain` if given, every such sort has
+/// `_by_key` and `_with_nans` rows, after the rows of its own it gives with `fn`.
+macro_rules! radix_sort {
+    (
+        $(#[$attr:meta])*
+        pub trait $Trait:ident: $base:ident, $title:literal $(, $plain:literal)?;
+        $(stepped $stepped:literal;)?
+        core |$arr:ident, $steps:ident| $core:expr;
+        $(
+            $(#[$note:meta])*
+            fn $name:ident $(<$($g:ident),*>)? ($($p:ident: $pt:ty),*): $what:literal
+                where { $($bound:tt)* }
+                $(panics $panics:literal)?
+                $(steps $lazy:tt)?
+                $(check |$c:ident| $check:block)?
+                => |$a:ident, $s:ident| $body:expr;
+        )*
+    ) => { paste::paste! { sort_variants! {
+        $(#[$attr])*
+        pub trait $Trait: $title;
+        $(stepped $stepped;)?
+
+        {
+            fn $base(): [] [$($plain)?]
+                where { T: $crate::algorithms::radix_key::RadixKey, }
+                => |arr, steps| {
+                    let $arr = arr;
+                    let $steps = steps;
+                    $core
+                }
+        }
+
+        $({
+            $(#[$note])*
+            fn $name $(<$($g),*>)? ($($p: $pt),*): [$what] []
+                where { $($bound)* }
+                $(panics $panics)?
+                $(steps $lazy)?
+                $(check |$c| $check)?
+                => |$a, $s| $body
+        })*
+
+        {
+            fn [<$base _by_key>]<K, F>(key: F): [" with a key extraction function"] [" by the keys `key` returns"]
+                where { F: FnMut(&T) -> K, K: $crate::algorithms::radix_key::RadixKey, }
+                steps {
+                    <K, F> (key: F) where {
+                        T: Clone + Send + 'static,
+                        F: FnMut(&T) -> K + Send + 'static,
+                        K: $crate::algorithms::radix_key::RadixKey,
+                    } => (key)
+                }
+                => |arr, steps| $crate::algorithms::recorder::sort_keyed(arr, &mut key, steps, |keyed, steps| {
+                    let $arr = keyed;
+                    let $steps = steps;
+                    $core;
+                })
+        }
+
+        {
+            /// The elements which are not comparable even to themselves, like `NaN`, go first or
+            /// last as `policy` says, or, if it does not allow them, make it return a `NanError`
+            /// without sorting anything.
+            fn [<$base _with_nans>](policy: $crate::algorithms::nan_policy::NanPolicy): [" with a `NaN` policy"] []
+                where { T: $crate::algorithms::radix_key::RadixKey, }
+                check |arr| { policy.check(arr)?; }
+                fails $crate::algorithms::nan_policy::NanError
+                => |arr, steps| {
+                    let mut key = |value: &T| policy.radix_key(value);
+                    $crate::algorithms::recorder::sort_keyed(arr, &mut key, steps, |keyed, steps| {
+                        let $arr = keyed;
+                        let $steps = steps;
+                        $core;
+                    })
+                }
+        }
+    }}};
+}
@@ -1,7 +1,73 @@
 //! # Mandatory readme
 //!
 //! This crate provides various different sorting algorithms both implemented directly on 
-//! any `Vec` implementing certain things and also as standalone functions.
+//! any slice (including `Vec`s, arrays and `Box<[T]>`s) of elements implementing certain things
+//! and also as standalone functions.
+//!
+//! The elements only need to implement `PartialOrd`, so `String`s and other types which are not
+//! `Copy` can be sorted too. Only the stepped and traced variants need them to be `Clone`, to record
+//! the steps.
+//!
+//! Every algorithm also comes with `_by` variants taking a comparator and `_by_key` variants taking a
+//! key extraction function, like the ones of the standard library, so elements without an order of
+//! their own can be sorted too. The non-comparison sorts (counting and radix sort) only have the
+//! `_by_key` ones.
+//!
+//! The comparison sorts additionally have `_by_cached_key` variants, which compute every key only
+//! once instead of on every comparison, for keys that are expensive to compute.
+//!
+//! Elements which are not comparable even to themselves, like `NaN`, have no place of their own in
+//! the partial order the plain variants sort by. The `_with_nans` variants take a `NanPolicy` which
+//! puts them first or last, or refuses to sort them, and the `_total` variants sort floats by their
+//! `total_cmp` instead.
+//!
+//! Every stepped variant comes with a `_traced` one too, which records the `SortEvent`s of the sort
+//! instead of copies of the array: the comparisons, swaps and writes, the ranges it works on, its
+//! pivots and the elements reaching their final position. `replay` rebuilds the steps from the array
+//! the sort started with and its trace.
+//!
+//! The `_observed` variants hand the events to a `SortObserver` instead, like a closure, as they
+//! happen, so they can be streamed or counted without being kept. The observer can stop the sort
+//! early by returning `ControlFlow::Break`, which leaves the slice partially sorted.
+//!
+//! The `_counted` variants count the comparisons, swaps, reads and writes of the sort instead, along
+//! with how deep it recursed and how many auxiliary buffers it allocated, and return them as
+//! `SortStats`. Unlike the `Duration` of the timed variants, they are the same on every run.
+//!
+//! The `_steps` functions turn the observed variants inside out: they return a `Steps` iterator
+//! over every event along with the array after it, which sorts only as far as it is advanced. It can
+//! be animated frame by frame or cut short with `take`, and dropping it cancels the sort. The sort
+//! runs on a thread of its own, so the elements have to be `Clone + Send + 'static`, and every step
+//! clones the whole array.
+//!
+//! The `_limited` variants bound sorts which might run for too long, like bogosort on untrusted
+//! input, by the `SortLimits` they take: a number of steps, a number of comparisons and a deadline.
+//! A sort exceeding any of them is aborted and returns a `SortAborted` error holding the partially
+//! sorted array and the `SortStats` up to that point.
+//!
+//! The randomized algorithms, bogosort, bogobogosort, bozosort and quicksort with random pivots,
+//! draw from the thread's random number generator, so no two runs are alike. Their `_with_rng`
+//! variants take the generator instead, and their `_with_seed` variants seed one, so a run can be
+//! reproduced exactly.
+//!
+//! To pick an algorithm at runtime, e.g. from a configuration, parse its name into an `Algorithm` and
+//! get a `Sorter` from it, which runs any of them through the same methods:
+//!
+//! ```rust
+//! use sort_it::prelude::*;
+//!
+//! fn main() {
+//!     let algorithm: Algorithm = "gnome_sort".parse().unwrap();
+//!
+//!     let mut v = vec![3, 1, 2];
+//!     algorithm.numeric_sorter().sort(&mut v); // sorts `v` via gnome sort.
+//!
+//!     for algorithm in Algorithm::ALL {
+//!         let steps = algorithm.numeric_sorter().sort_stepped(&mut vec![3, 1, 2]);
+//!         println!("{} takes {} steps", algorithm, steps.len());
+//!     }
+//! }
+//! ```
 //! 
 //! # Examples
 //!
@@ -17,6 +83,11 @@
 //!     
 //!     v.gnome_sort(); // sorts `v` via gnome sort (with the trait implementation).
 //!     
+//!     let mut a = [5, 4, 3, 2, 1];
+//!     a[1..4].insertion_sort(); // sorts only the middle of `a` in place, leaving `[5, 2, 3, 4, 1]`.
+//!     
+//!     v.heap_sort_by(|a, b| b.cmp(a)); // sorts `v` in descending order via heap sort.
+//!     
 //!     println!("sorted v: {:?}", v);
 //! }
 //! ```
@@ -40,24 +111,81 @@
 //! }
 //! ```
 //! 
+//! # Terminal visualizer
+//!
+//! With the `cli` feature, the crate comes with a `sort-it` binary, which animates any of the
+//! algorithms as a bar chart in the terminal, highlighting the compared bars in yellow and the
+//! swapped ones in red. It takes the algorithm, the number of elements and their distribution
+//! (`random`, `sorted`, `reversed`, `nearly-sorted` or `few-unique`), along with the delay between
+//! two frames in milliseconds:
+//!
+//! ```sh
+//! cargo run --features cli -- quicksort 60 random --delay 10
+//! ```
+//!
+//! `sort-it --help` lists the other options.
+//!
+//! # Rendering
+//!
+//! The `render` module turns the steps of the stepped variants (but for the tuples of selection and
+//! tree sort) into an animated bar chart, to embed in documentation or slides: `write_svg` writes
+//! an SVG animated with SMIL and `write_gif` writes a looping GIF, both with the width, height,
+//! colors and frame rate of the `RenderOptions`. The steps `highlighted_steps` rebuilds from a
+//! trace come with the compared and swapped elements of every step, which are drawn in colors of
+//! their own. Everything is written in Rust alone, so it works on a headless CI machine too.
+//!
+//! `write_html` writes a single HTML page playing traces instead, which can be opened from a CI
+//! artifact in any browser. It has buttons to play, pause and step forward and back, a slider for
+//! the speed and a live count of the comparisons, swaps and writes so far. Given the traces of
+//! several sorts of the same array, like `gnome_sort` and `insertion_sort`, it plays them side by
+//! side, step by step.
+//!
 //! # Implemented sorting algorithms:
 //! 
+//! * Bogobogosort
 //! * Bogosort
+//! * Bozosort
 //! * Bubble Sort
+//! * Cocktail Shaker Sort
+//! * Comb Sort
+//! * Counting Sort
 //! * Gnome Sort
+//! * Heap Sort
 //! * Insertion Sort
+//! * Introsort
+//! * LSD Radix Sort
 //! * Merge Sort
+//! * MSD Radix Sort
+//! * Odd-Even Sort
+//! * Pattern-Defeating Quicksort
+//! * Permutation Sort
+//! * Quicksort
 //! * Selection Sort
+//! * Shell Sort
 //! * Slowsort
 //! * Stooge Sort
+//! * Timsort
+//! * Tree Sort
+//!
+//! Their complexities, stability and other properties are available through `catalog()` and
+//! `Algorithm::info`. The terrible ones, which only ever stumble upon the sorted permutation, also
+//! have the expected number of shuffles, swaps or permutations they take through
+//! `Algorithm::expected_iterations`, e.g. `n! - 1` shuffles for bogosort.
 //! 
 //! Have fun sorting things in different ways.
 
+// Explicit `return`s are the house style.
+#![allow(clippy::needless_return)]
+// The stepped and timed variants return plain tuples, wrapped in a `Result` where they can fail.
+#![allow(clippy::type_complexity)]
+
 #[cfg(test)]
 mod tests;
 
 pub mod algorithms;
 
+pub mod render;
+
 pub mod prelude {
     pub use crate::algorithms::*;
 }
@@ -1,4 +1,389 @@
+//! The `sort-it` binary, which animates a sorting algorithm as a bar chart in the terminal.
+//!
+//! It is only built with the `cli` feature, e.g. by
+//! `cargo run --features cli -- gnome_sort 40 random --delay 10`.
+
+// Explicit `return`s are the house style.
+#![allow(clippy::needless_return)]
+
+use std::env;
+use std::io::{ self, Write };
+use std::ops::ControlFlow;
+use std::process;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+use rand::prelude::*;
 use sort_it::prelude::*;
 
-fn main() { }
+const COMPARED: &str = "\x1b[33m";
+const CHANGED: &str = "\x1b[31m";
+const SORTED: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+/// Returns the help message, which lists the algorithms and distributions to pick from.
+fn usage() -> String {
+    let algorithms: Vec<&str> = Algorithm::ALL.iter().map(|algorithm| algorithm.name()).collect();
+
+    return format!("\
+usage: sort-it <algorithm> <size> <distribution> [options]
+
+Animates sorting <size> elements with <algorithm> as a bar chart, highlighting the compared bars in
+yellow, the swapped and written ones in red and the ones in their final position in green.
+
+algorithms: {}
+
+distributions: random, sorted, reversed, nearly-sorted, few-unique
+
+options:
+    --delay <ms>      the delay between two frames, 20 by default
+    --height <rows>   the height of the chart, 20 by default
+    --seed <seed>     the seed of the input, which is different on every run otherwise
+    -h, --help        prints this message", algorithms.join(", "));
+}
+
+/// The distributions of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Distribution {
+    /// The numbers from 1 to the size, shuffled.
+    Random,
+    /// The numbers from 1 to the size, in order.
+    Sorted,
+    /// The numbers from 1 to the size, in reverse order.
+    Reversed,
+    /// The numbers from 1 to the size, in order but for a few swapped neighbours.
+    NearlySorted,
+    /// Four different numbers up to the size, shuffled.
+    FewUnique,
+}
+
+impl Distribution {
+    /// Generates `size` elements of the distribution, drawing from `rng`.
+    ///
+    /// The size has to fit into a `u32`, which `Options::parse` makes sure of.
+    fn generate(self, size: usize, rng: &mut StdRng) -> Vec<u32> {
+        let mut arr: Vec<u32> = (1..=size as u32).collect();
+
+        match self {
+            Distribution::Random => arr.shuffle(rng),
+            Distribution::Sorted => {},
+            Distribution::Reversed => arr.reverse(),
+            // There are no neighbours to swap with fewer than two elements.
+            Distribution::NearlySorted if size < 2 => {},
+            Distribution::NearlySorted => {
+                for _ in 0..size.div_ceil(10) {
+                    let i = rng.gen_range(0..size - 1);
+                    arr.swap(i, i + 1);
+                }
+            },
+            Distribution::FewUnique => {
+                arr = (0..size).map(|i| ((i % 4 + 1) * size).div_ceil(4) as u32).collect();
+                arr.shuffle(rng);
+            },
+        }
+
+        return arr;
+    }
+}
+
+impl FromStr for Distribution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return match s.trim().to_lowercase().replace([' ', '_'], "-").as_str() {
+            "random" => Ok(Distribution::Random),
+            "sorted" => Ok(Distribution::Sorted),
+            "reversed" => Ok(Distribution::Reversed),
+            "nearly-sorted" => Ok(Distribution::NearlySorted),
+            "few-unique" => Ok(Distribution::FewUnique),
+            _ => Err(format!("there is no distribution called `{}`", s)),
+        };
+    }
+}
+
+/// The options the binary was run with.
+struct Options {
+    algorithm: Algorithm,
+    size: usize,
+    distribution: Distribution,
+    delay: Duration,
+    height: usize,
+    seed: Option<u64>,
+}
+
+impl Options {
+    /// Parses the options from the arguments, without the name of the binary.
+    fn parse<I>(args: I) -> Result<Options, String>
+        where I: IntoIterator<Item = String>,
+    {
+        let mut positional = vec![];
+        let mut delay = Duration::from_millis(20);
+        let mut height = 20;
+        let mut seed = None;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--delay" => delay = Duration::from_millis(parse_value(&arg, args.next())?),
+                "--height" => height = parse_value(&arg, args.next())?,
+                "--seed" => seed = Some(parse_value(&arg, args.next())?),
+                _ if arg.starts_with("--") => return Err(format!("there is no option `{}`", arg)),
+                _ => positional.push(arg),
+            }
+        }
+
+        let [algorithm, size, distribution] = <[String; 3]>::try_from(positional)
+            .map_err(|_| "expected an algorithm, a size and a distribution".to_string())?;
+
+        if height == 0 {
+            return Err("the chart needs a height of at least 1".to_string());
+        }
+
+        // The elements are the numbers from 1 to the size, which are `u32`s.
+        let size: usize = parse_value("<size>", Some(size))?;
+        if u32::try_from(size).is_err() {
+            return Err(format!("the size can be at most {}", u32::MAX));
+        }
+
+        return Ok(Options {
+            algorithm: algorithm.parse().map_err(|err: ParseAlgorithmError| err.to_string())?,
+            size,
+            distribution: distribution.parse()?,
+            delay,
+            height,
+            seed,
+        });
+    }
+}
+
+/// Parses the value of the argument `name`, which is missing if the argument came last.
+fn parse_value<T>(name: &str, value: Option<String>) -> Result<T, String>
+    where T: FromStr,
+{
+    let value = value.ok_or_else(|| format!("missing the value of `{}`", name))?;
+    return value.parse().map_err(|_| format!("invalid value `{}` for `{}`", value, name));
+}
+
+/// The bar chart the array is drawn as, along with what the events of the sort told so far.
+struct Chart {
+    title: String,
+    arr: Vec<u32>,
+    max: u32,
+    height: usize,
+    sorted: Vec<bool>,
+    stats: SortStats,
+}
+
+impl Chart {
+    fn new(arr: Vec<u32>, options: &Options) -> Self {
+        let title = format!("{}, {} elements", options.algorithm.info().name, arr.len());
+        let max = arr.iter().copied().max().unwrap_or(1);
+        let sorted = vec![false; arr.len()];
+
+        return Chart { title, arr, max, height: options.height, sorted, stats: SortStats::default() };
+    }
+
+    /// Applies `event` to the chart, returning the bars to highlight, along with their colors, or
+    /// nothing if the event is not worth a frame of its own.
+    fn apply(&mut self, event: SortEvent<&u32>) -> Vec<(usize, &'static str)> {
+        match event {
+            SortEvent::Compare(i, j) => {
+                self.stats.comparisons += 1;
+                return vec![(i, COMPARED), (j, COMPARED)];
+            },
+            SortEvent::Swap(i, j) => {
+                self.arr.swap(i, j);
+                self.stats.swaps += 1;
+                return vec![(i, CHANGED), (j, CHANGED)];
+            },
+            SortEvent::Write(i, &value) => {
+                self.arr[i] = value;
+                self.stats.writes += 1;
+                return vec![(i, CHANGED)];
+            },
+            SortEvent::Read(i) => {
+                self.stats.reads += 1;
+                return vec![(i, COMPARED)];
+            },
+            SortEvent::MarkSorted(i) => {
+                self.sorted[i] = true;
+                return vec![];
+            },
+            SortEvent::EnterRange(..) | SortEvent::Pivot(..) => return vec![],
+        }
+    }
+
+    /// Draws the chart over the previous one, with the bars in `highlights` in their colors.
+    fn draw(&self, highlights: &[(usize, &str)]) -> io::Result<()> {
+        // Every line is cleared to its end, in case the previous frame was wider.
+        let mut frame = format!("\x1b[H{}\x1b[K\n", self.title);
+        frame += &format!(
+            "comparisons: {}, swaps: {}, reads: {}, writes: {}\x1b[K\n",
+            self.stats.comparisons, self.stats.swaps, self.stats.reads, self.stats.writes,
+        );
+
+        for row in (1..=self.height).rev() {
+            for (i, &value) in self.arr.iter().enumerate() {
+                // A bar reaches up to its share of the height, rounded up, so none is empty.
+                let bar = if (value as usize * self.height).div_ceil(self.max as usize) >= row { '█' } else { ' ' };
+                let color = highlights.iter()
+                    .find(|&&(j, _)| j == i)
+                    .map(|&(_, color)| color)
+                    .or(if self.sorted[i] { Some(SORTED) } else { None });
+
+                match color {
+                    Some(color) if bar != ' ' => {
+                        frame += color;
+                        frame.push(bar);
+                        frame += RESET;
+                    },
+                    _ => frame.push(bar),
+                }
+            }
+            frame += "\x1b[K\n";
+        }
+
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(frame.as_bytes())?;
+        return stdout.flush();
+    }
+}
+
+/// Animates sorting the input the options describe, frame by frame.
+fn run(options: &Options) -> io::Result<()> {
+    let mut rng = match options.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let mut arr = options.distribution.generate(options.size, &mut rng);
+    let mut chart = Chart::new(arr.clone(), options);
+
+    // The screen is cleared once, and every frame is drawn over the previous one from then on.
+    print!("\x1b[2J");
+    chart.draw(&[])?;
+
+    // The sort is only ever stopped by an error, which is kept for after it.
+    let mut result = Ok(());
+    let _ = options.algorithm.numeric_sorter().sort_observed(&mut arr, &mut |event: SortEvent<&u32>| {
+        let highlights = chart.apply(event);
+        if highlights.is_empty() {
+            return ControlFlow::Continue(());
+        }
+
+        // A terminal which went away, e.g. by piping into `head`, stops the sort.
+        if let Err(err) = chart.draw(&highlights) {
+            result = Err(err);
+            return ControlFlow::Break(());
+        }
+
+        thread::sleep(options.delay);
+        return ControlFlow::Continue(());
+    });
+    result?;
+
+    // Every bar is in its final position once the sort is done.
+    chart.sorted.fill(true);
+    return chart.draw(&[]);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", usage());
+        return;
+    }
+
+    let options = match Options::parse(args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, usage());
+            process::exit(2);
+        },
+    };
+
+    if let Err(err) = run(&options) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DISTRIBUTIONS: [Distribution; 5] = [
+        Distribution::Random,
+        Distribution::Sorted,
+        Distribution::Reversed,
+        Distribution::NearlySorted,
+        Distribution::FewUnique,
+    ];
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        return Options::parse(args.iter().map(|arg| arg.to_string()));
+    }
+
+    #[test]
+    fn parse_options() {
+        let options = parse(&["gnome_sort", "40", "nearly sorted", "--delay", "5", "--seed", "7"]).unwrap();
+        assert_eq!(options.algorithm, Algorithm::GnomeSort);
+        assert_eq!(options.size, 40);
+        assert_eq!(options.distribution, Distribution::NearlySorted);
+        assert_eq!(options.delay, Duration::from_millis(5));
+        assert_eq!(options.height, 20);
+        assert_eq!(options.seed, Some(7));
+
+        let options = parse(&["--height", "3", "quicksort", "0", "few_unique"]).unwrap();
+        assert_eq!(options.algorithm, Algorithm::Quicksort);
+        assert_eq!(options.size, 0);
+        assert_eq!(options.distribution, Distribution::FewUnique);
+        assert_eq!(options.height, 3);
+        assert_eq!(options.seed, None);
+    }
+
+    #[test]
+    fn parse_invalid_options() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["gnome_sort", "40"]).is_err());
+        assert!(parse(&["gnome_sort", "40", "random", "sorted"]).is_err());
+        assert!(parse(&["no_sort", "40", "random"]).is_err());
+        assert!(parse(&["gnome_sort", "-1", "random"]).is_err());
+        assert!(parse(&["gnome_sort", "40", "shuffled"]).is_err());
+        assert!(parse(&["gnome_sort", "40", "random", "--speed", "1"]).is_err());
+        assert!(parse(&["gnome_sort", "40", "random", "--delay"]).is_err());
+        assert!(parse(&["gnome_sort", "40", "random", "--height", "0"]).is_err());
+
+        let too_large = (u32::MAX as u64 + 1).to_string();
+        assert!(parse(&["gnome_sort", &too_large, "random"]).is_err());
+        assert!(parse(&["gnome_sort", &u32::MAX.to_string(), "random"]).is_ok());
+    }
+
+    #[test]
+    fn generate() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for distribution in DISTRIBUTIONS {
+            for size in [0, 1, 2, 3, 10, 100] {
+                let arr = distribution.generate(size, &mut rng);
+                assert_eq!(arr.len(), size, "{:?}", distribution);
+                assert!(arr.iter().all(|&x| 1 <= x && x as usize <= size), "{:?}", distribution);
+
+                let mut sorted = arr.clone();
+                sorted.sort();
+                match distribution {
+                    Distribution::FewUnique => {
+                        sorted.dedup();
+                        assert!(sorted.len() <= 4);
+                    },
+                    _ => assert_eq!(sorted, (1..=size as u32).collect::<Vec<u32>>(), "{:?}", distribution),
+                }
 
+                match distribution {
+                    Distribution::Sorted => assert_eq!(arr, sorted),
+                    Distribution::Reversed => assert!(arr.iter().rev().eq(sorted.iter())),
+                    _ => {},
+                }
+            }
+        }
+    }
+}
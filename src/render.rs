@@ -0,0 +1,609 @@
+//! Renders the steps of a sort as an animated bar chart, either as an SVG, as a GIF or as an HTML
+//! player, without anything but Rust, so it works on a headless machine too.
+//!
+//! The steps are the `Vec<Vec<T>>` of the stepped variants, which all but selection sort and tree
+//! sort record, their steps being tuples, or the ones `highlighted_steps` rebuilds from a trace of
+//! any sort along with the elements every step compares and changes:
+//!
+//! ```rust
+//! use sort_it::prelude::*;
+//! use sort_it::render::{ self, RenderOptions };
+//!
+//! let (_, steps) = bubble_sort_stepped(vec![3, 1, 2]);
+//!
+//! let mut svg = vec![];
+//! render::write_svg(&mut svg, &steps, None, &RenderOptions::default()).unwrap();
+//!
+//! let arr = vec![3, 1, 2];
+//! let (_, trace) = insertion_sort_traced(arr.clone());
+//! let (steps, highlights) = render::highlighted_steps(&arr, &trace);
+//!
+//! let mut gif = vec![];
+//! render::write_gif(&mut gif, &steps, Some(&highlights), &RenderOptions::default()).unwrap();
+//! ```
+//!
+//! `write_html` plays traces instead, in a single HTML page with controls to play, pause and step
+//! through them, and puts the traces of several sorts of the same array side by side.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{ self, Write };
+use crate::algorithms::{ NanPolicy, SortEvent };
+
+/// A color of the rendered animations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    /// Returns the color with the given red, green and blue components.
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        return Color { r, g, b };
+    }
+}
+
+/// Displays the color as `#rrggbb`, like SVG takes it.
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// An element a step highlights, by its index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Highlight {
+    /// The element was compared, or read on its own.
+    Compared(usize),
+    /// The element was swapped, or written.
+    Changed(usize),
+}
+
+/// The options of the rendered animations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RenderOptions {
+    /// The width of the animation in pixels, which the bars share.
+    pub width: u32,
+    /// The height of the animation in pixels, which the tallest bars take up.
+    pub height: u32,
+    /// The number of steps shown every second.
+    ///
+    /// GIFs only take the time a frame is shown in hundredths of a second, so their frame rates are
+    /// rounded to 100 divided by a whole number, and most viewers slow down anything above 50.
+    pub frame_rate: u32,
+    /// The color behind the bars.
+    pub background: Color,
+    /// The color of the bars which are not highlighted.
+    pub bar: Color,
+    /// The color of the bars a step compares.
+    pub compared: Color,
+    /// The color of the bars a step changes, which wins over the compared one.
+    pub changed: Color,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        return RenderOptions {
+            width: 640,
+            height: 360,
+            frame_rate: 25,
+            background: Color::rgb(0xff, 0xff, 0xff),
+            bar: Color::rgb(0x60, 0x60, 0x60),
+            compared: Color::rgb(0xf0, 0xb0, 0x20),
+            changed: Color::rgb(0xe0, 0x30, 0x30),
+        };
+    }
+}
+
+impl RenderOptions {
+    /// Returns the colors, in the order of the indices `Chart::color` returns.
+    fn palette(&self) -> [Color; 4] {
+        return [self.background, self.bar, self.compared, self.changed];
+    }
+}
+
+/// Rebuilds the steps of a sort from the array it started with and its trace, along with the
+/// elements every step highlights.
+///
+/// Unlike `replay`, it records a step for every comparison and read too, not only for every swap
+/// and write, so the animation shows the sort looking at the elements before it moves them.
+pub fn highlighted_steps<T>(initial: &[T], trace: &[SortEvent<T>]) -> (Vec<Vec<T>>, Vec<Vec<Highlight>>)
+    where T: Clone,
+{
+    let mut arr = initial.to_vec();
+    let mut steps = vec![arr.clone()];
+    let mut highlights = vec![vec![]];
+
+    for event in trace {
+        let highlight = match event {
+            SortEvent::Compare(i, j) => vec![Highlight::Compared(*i), Highlight::Compared(*j)],
+            SortEvent::Read(i) => vec![Highlight::Compared(*i)],
+            SortEvent::Swap(i, j) => {
+                arr.swap(*i, *j);
+                vec![Highlight::Changed(*i), Highlight::Changed(*j)]
+            },
+            SortEvent::Write(i, value) => {
+                arr[*i] = value.clone();
+                vec![Highlight::Changed(*i)]
+            },
+            _ => continue,
+        };
+
+        steps.push(arr.clone());
+        highlights.push(highlight);
+    }
+
+    return (steps, highlights);
+}
+
+/// Writes the steps as an animated SVG, which shows every step for `1 / frame_rate` seconds and
+/// starts over after the last one.
+///
+/// Every bar is a single `rect` with a SMIL animation of its height and color, so the SVG grows
+/// with the number of bars times the number of steps, but not with the size of the image. Like
+/// for the GIFs, the heights of the bars are the ranks of the elements among the ones of the
+/// first step, rather than their values.
+///
+/// # Panics
+///
+/// Panics if the width, the height or the frame rate is zero, or if there are highlights, but
+/// not as many as steps.
+pub fn write_svg<T, W>(mut out: W, steps: &[Vec<T>], highlights: Option<&[Vec<Highlight>]>, options: &RenderOptions) -> io::Result<()>
+    where T: PartialOrd,
+          W: Write,
+{
+    let chart = Chart::new(steps, highlights, options);
+    let palette = options.palette();
+    let (width, height) = (options.width, options.height);
+    let duration = steps.len() as f64 / options.frame_rate as f64;
+
+    writeln!(out, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#, width, height, width, height)?;
+    writeln!(out, r#"<rect width="100%" height="100%" fill="{}"/>"#, options.background)?;
+
+    // The bars grow upwards from the bottom, so the chart is flipped upside down.
+    writeln!(out, r#"<g transform="translate(0 {}) scale(1 -1)">"#, height)?;
+    for i in 0..chart.bars() {
+        let (x0, x1) = chart.columns(i);
+        let heights: Vec<u32> = (0..steps.len()).map(|step| chart.height(step, i)).collect();
+        let colors: Vec<Color> = (0..steps.len()).map(|step| palette[chart.color(step, i) as usize]).collect();
+
+        write!(out, r#"<rect x="{}" width="{}" height="{}" fill="{}">"#, x0, x1 - x0, heights[0], colors[0])?;
+        animate(&mut out, "height", &heights, duration)?;
+        animate(&mut out, "fill", &colors, duration)?;
+        writeln!(out, "</rect>")?;
+    }
+    writeln!(out, "</g>")?;
+
+    return writeln!(out, "</svg>");
+}
+
+/// Auxiliary function writing the SMIL animation of `attribute` through `values`, one for every
+/// step, unless it never changes anyway.
+fn animate<V, W>(out: &mut W, attribute: &str, values: &[V], duration: f64) -> io::Result<()>
+    where V: PartialEq + fmt::Display,
+          W: Write,
+{
+    if values.windows(2).all(|pair| pair[0] == pair[1]) {
+        return Ok(());
+    }
+
+    let values: Vec<String> = values.iter().map(V::to_string).collect();
+    return write!(
+        out,
+        r#"<animate attributeName="{}" values="{}" dur="{}s" calcMode="discrete" repeatCount="indefinite"/>"#,
+        attribute, values.join(";"), duration,
+    );
+}
+
+/// Writes the steps as an animated GIF, frame by frame, which shows every step for
+/// `1 / frame_rate` seconds and starts over after the last one.
+///
+/// The GIF only has the four colors of the options, so it stays small for all of its frames. The
+/// heights of the bars are the ranks of the elements among the ones of the first step, rather
+/// than their values, so any elements with an order can be drawn.
+///
+/// # Panics
+///
+/// Panics if the width, the height or the frame rate is zero, if the width or the height is more
+/// than 65535, which is as large as a GIF gets, or if there are highlights, but not as many as
+/// steps.
+pub fn write_gif<T, W>(mut out: W, steps: &[Vec<T>], highlights: Option<&[Vec<Highlight>]>, options: &RenderOptions) -> io::Result<()>
+    where T: PartialOrd,
+          W: Write,
+{
+    let chart = Chart::new(steps, highlights, options);
+    assert!(
+        options.width <= u16::MAX as u32 && options.height <= u16::MAX as u32,
+        "a GIF is at most 65535 pixels wide and high, got {}x{}", options.width, options.height,
+    );
+    let (width, height) = (options.width as u16, options.height as u16);
+
+    // The logical screen, with a global color table of the four colors.
+    out.write_all(b"GIF89a")?;
+    out.write_all(&width.to_le_bytes())?;
+    out.write_all(&height.to_le_bytes())?;
+    out.write_all(&[0xf1, 0, 0])?;
+    for color in options.palette() {
+        out.write_all(&[color.r, color.g, color.b])?;
+    }
+
+    // The Netscape extension, which makes the GIF loop forever.
+    out.write_all(&[0x21, 0xff, 0x0b])?;
+    out.write_all(b"NETSCAPE2.0")?;
+    out.write_all(&[0x03, 0x01, 0x00, 0x00, 0x00])?;
+
+    let delay = ((100 + options.frame_rate / 2) / options.frame_rate).max(1) as u16;
+    let (w, h) = (width as usize, height as usize);
+    let mut pixels = vec![0; w * h];
+    let mut previous: Option<Vec<u8>> = None;
+
+    // Even no steps at all make a frame, since a GIF needs one.
+    for step in 0..steps.len().max(1) {
+        pixels.fill(0);
+        if step < steps.len() {
+            for i in 0..chart.bars() {
+                let (x0, x1) = chart.columns(i);
+                let color = chart.color(step, i);
+                for y in (options.height - chart.height(step, i))..options.height {
+                    pixels[y as usize * w + x0 as usize..y as usize * w + x1 as usize].fill(color);
+                }
+            }
+        }
+
+        // Every frame is drawn on top of the previous one, so only the columns which changed
+        // since are encoded, or a single one if none did, to keep the delay.
+        let (left, right) = match &previous {
+            Some(previous) => {
+                let changed = |x: &usize| (0..h).any(|y| pixels[y * w + x] != previous[y * w + x]);
+                match (0..w).find(changed) {
+                    Some(left) => (left, (0..w).rev().find(changed).unwrap() + 1),
+                    None => (0, 1),
+                }
+            },
+            None => (0, w),
+        };
+        let region: Vec<u8> = (0..h).flat_map(|y| pixels[y * w + left..y * w + right].iter().copied()).collect();
+
+        // The graphic control extension with the delay, keeping the previous frame, and the
+        // image descriptor of the columns.
+        out.write_all(&[0x21, 0xf9, 0x04, 0x04])?;
+        out.write_all(&delay.to_le_bytes())?;
+        out.write_all(&[0x00, 0x00, 0x2c])?;
+        out.write_all(&(left as u16).to_le_bytes())?;
+        out.write_all(&[0, 0])?;
+        out.write_all(&((right - left) as u16).to_le_bytes())?;
+        out.write_all(&height.to_le_bytes())?;
+        out.write_all(&[0x00, LZW_MIN_CODE_SIZE as u8])?;
+
+        for block in lzw(&region).chunks(255) {
+            out.write_all(&[block.len() as u8])?;
+            out.write_all(block)?;
+        }
+        out.write_all(&[0x00])?;
+
+        previous = Some(pixels.clone());
+    }
+
+    return out.write_all(&[0x3b]);
+}
+
+/// The number of bits of the color indices, which GIF needs to be at least 2.
+const LZW_MIN_CODE_SIZE: u32 = 2;
+
+/// Auxiliary function compressing the color indices of a frame with the LZW variant of GIF,
+/// which starts with codes one bit wider than the indices and widens them as its table grows, up to
+/// 12 bits, after which it clears the table and starts over.
+fn lzw(indices: &[u8]) -> Vec<u8> {
+    let clear = 1 << LZW_MIN_CODE_SIZE;
+    let end = clear + 1;
+
+    let mut bits = Bits::default();
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut width = LZW_MIN_CODE_SIZE + 1;
+
+    bits.write(clear, width);
+
+    let mut prefix: Option<u16> = None;
+    for &index in indices {
+        let code = match prefix {
+            None => index as u16,
+            Some(prefix) => match table.get(&(prefix, index)) {
+                Some(&code) => code,
+                None => {
+                    bits.write(prefix, width);
+
+                    if next == 4096 {
+                        bits.write(clear, width);
+                        table.clear();
+                        next = end + 1;
+                        width = LZW_MIN_CODE_SIZE + 1;
+                    } else {
+                        table.insert((prefix, index), next);
+                        next += 1;
+
+                        // The decoder adds every code one code later than the encoder does, so it
+                        // only needs the wider codes after the one after the widest code.
+                        if next > 1 << width && width < 12 {
+                            width += 1;
+                        }
+                    }
+
+                    index as u16
+                },
+            },
+        };
+        prefix = Some(code);
+    }
+
+    if let Some(prefix) = prefix {
+        bits.write(prefix, width);
+
+        // The decoder adds a code for the last one too, which may widen the end code.
+        if next < 4096 && next + 1 > 1 << width && width < 12 {
+            width += 1;
+        }
+    }
+    bits.write(end, width);
+
+    return bits.finish();
+}
+
+/// The bits the LZW codes are packed into, from the least significant bit of every byte on.
+#[derive(Default)]
+struct Bits {
+    bytes: Vec<u8>,
+    buffer: u32,
+    count: u32,
+}
+
+impl Bits {
+    fn write(&mut self, code: u16, width: u32) {
+        self.buffer |= (code as u32) << self.count;
+        self.count += width;
+
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+
+        return self.bytes;
+    }
+}
+
+/// Writes the traces of sorts of `initial` as a single HTML page, which plays them in a bar chart
+/// each, side by side, with the width, height and colors of the options.
+///
+/// The page needs nothing but a browser, as the steps are rebuilt from the traces by the script
+/// embedded in it. It has buttons to play and pause at the frame rate of the options and to step
+/// forward and back, along with a slider for the speed, and counts the comparisons, swaps and
+/// writes of every sort up to the current step. Two sorts of the same array, like gnome sort and
+/// insertion sort, thus show how they differ step by step:
+///
+/// ```rust
+/// use sort_it::prelude::*;
+/// use sort_it::render::{ self, RenderOptions };
+///
+/// let arr = vec![5, 2, 4, 1, 3];
+/// let (_, gnome) = gnome_sort_traced(arr.clone());
+/// let (_, insertion) = insertion_sort_traced(arr.clone());
+///
+/// let mut html = vec![];
+/// let traces = [("gnome_sort", &gnome[..]), ("insertion_sort", &insertion[..])];
+/// render::write_html(&mut html, &arr, &traces, &RenderOptions::default()).unwrap();
+/// ```
+///
+/// Like for the SVGs and the GIFs, the heights of the bars are the ranks of the elements among the
+/// ones of `initial`, and the steps of the animation are the comparisons, swaps, writes and reads
+/// of the traces, while their other events are left out.
+///
+/// # Panics
+///
+/// Panics if there are no traces, if the width, the height or the frame rate is zero, or if an
+/// event of a trace is past the end of `initial`.
+pub fn write_html<T, W>(mut out: W, initial: &[T], traces: &[(&str, &[SortEvent<T>])], options: &RenderOptions) -> io::Result<()>
+    where T: PartialOrd,
+          W: Write,
+{
+    assert!(!traces.is_empty(), "there must be at least one trace to play");
+    assert!(options.width > 0 && options.height > 0, "an animation needs a width and a height of at least 1");
+    assert!(options.frame_rate > 0, "an animation needs a frame rate of at least 1");
+
+    let title: Vec<String> = traces.iter().map(|(name, _)| html_escape(name)).collect();
+    let ranked = ranked(initial);
+    let ranks: Vec<String> = initial.iter().map(|value| rank(&ranked, value).to_string()).collect();
+
+    // Every event is three numbers, its kind and its indices, or the rank of the value it writes.
+    let traces: Vec<String> = traces.iter().map(|(name, trace)| {
+        let events: Vec<String> = trace.iter()
+            .filter_map(|event| {
+                let (kind, a, b) = match event {
+                    SortEvent::Compare(i, j) => (0, *i, *j),
+                    SortEvent::Swap(i, j) => (1, *i, *j),
+                    SortEvent::Write(i, value) => (2, *i, rank(&ranked, value)),
+                    SortEvent::Read(i) => (3, *i, 0),
+                    _ => return None,
+                };
+                assert!(a < initial.len() && (kind == 2 || b < initial.len()), "the event {} of `{}` is past the end of the array", kind, name);
+
+                return Some(format!("{},{},{}", kind, a, b));
+            })
+            .collect();
+
+        return format!(r#"{{"name":{},"events":[{}]}}"#, json_string(name), events.join(","));
+    }).collect();
+
+    let data = format!(
+        r#"{{"width":{},"height":{},"frameRate":{},"colors":{{"background":"{}","bar":"{}","compared":"{}","changed":"{}"}},"initial":[{}],"traces":[{}]}}"#,
+        options.width, options.height, options.frame_rate,
+        options.background, options.bar, options.compared, options.changed,
+        ranks.join(","), traces.join(","),
+    );
+
+    let page = fill(include_str!("render/player.html"), &[("title", &title.join(" vs. ")), ("data", &data)]);
+    return out.write_all(page.as_bytes());
+}
+
+/// Auxiliary function filling the `{{name}}` placeholders of `template` in with their values in a
+/// single pass, so the placeholders a value contains, e.g. in the name of a trace, are left as they
+/// are.
+fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        filled += &rest[..start];
+        rest = &rest[start + 2..];
+
+        let value = values.iter().find(|(name, _)| {
+            rest.strip_prefix(name).is_some_and(|after| after.starts_with("}}"))
+        });
+        match value {
+            Some((name, value)) => {
+                filled += value;
+                rest = &rest[name.len() + 2..];
+            },
+            None => filled += "{{",
+        }
+    }
+    filled += rest;
+
+    return filled;
+}
+
+/// Auxiliary function quoting `s` as a JSON string, which also escapes `<`, so it cannot end the
+/// script it is embedded in.
+fn json_string(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted += "\\\"",
+            '\\' => quoted += "\\\\",
+            '<' => quoted += "\\u003c",
+            c if c.is_control() => quoted += &format!("\\u{:04x}", c as u32),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+
+    return quoted;
+}
+
+/// Auxiliary function escaping the characters of `s` which HTML would take as markup.
+fn html_escape(s: &str) -> String {
+    return s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;");
+}
+
+/// The bar chart the steps are drawn as, which both renderers share.
+struct Chart<'a, T> {
+    steps: &'a [Vec<T>],
+    highlights: Option<&'a [Vec<Highlight>]>,
+    options: &'a RenderOptions,
+    /// The elements of the first step, in order, which the elements of every step are ranked
+    /// among.
+    ranked: Vec<&'a T>,
+}
+
+impl<'a, T> Chart<'a, T>
+    where T: PartialOrd,
+{
+    fn new(steps: &'a [Vec<T>], highlights: Option<&'a [Vec<Highlight>]>, options: &'a RenderOptions) -> Self {
+        assert!(options.width > 0 && options.height > 0, "an animation needs a width and a height of at least 1");
+        assert!(options.frame_rate > 0, "an animation needs a frame rate of at least 1");
+        if let Some(highlights) = highlights {
+            assert_eq!(highlights.len(), steps.len(), "there must be as many highlights as there are steps");
+        }
+
+        let ranked = steps.first().map(|first| ranked(first)).unwrap_or_default();
+
+        return Chart { steps, highlights, options, ranked };
+    }
+
+    /// Returns the number of bars.
+    fn bars(&self) -> usize {
+        return self.ranked.len();
+    }
+
+    /// Returns the columns of the bar at `i`, from `x0` up to `x1`, leaving a gap to the next bar
+    /// if there is room for one.
+    fn columns(&self, i: usize) -> (u32, u32) {
+        let column = |i: usize| (i as u64 * self.options.width as u64 / self.bars() as u64) as u32;
+        let (x0, x1) = (column(i), column(i + 1));
+
+        if x1 - x0 >= 4 {
+            return (x0, x1 - 1);
+        }
+        return (x0, x1);
+    }
+
+    /// Returns the height of the bar at `i` in `step` in pixels, which is its share of the height
+    /// by the rank of its element, so even the smallest element has a bar.
+    ///
+    /// The steps of some stepped variants, like the merges of merge sort, are only a part of the
+    /// array, which leaves out the bars past their end.
+    fn height(&self, step: usize, i: usize) -> u32 {
+        let value = match self.steps[step].get(i) {
+            Some(value) => value,
+            None => return 0,
+        };
+        let rank = rank(&self.ranked, value) as u64;
+
+        return ((rank + 1) * self.options.height as u64).div_ceil(self.bars() as u64) as u32;
+    }
+
+    /// Returns the index of the color of the bar at `i` in `step` in the palette of the options.
+    fn color(&self, step: usize, i: usize) -> u8 {
+        let highlights = match self.highlights {
+            Some(highlights) => &highlights[step],
+            None => return 1,
+        };
+
+        if highlights.contains(&Highlight::Changed(i)) {
+            return 3;
+        }
+        if highlights.contains(&Highlight::Compared(i)) {
+            return 2;
+        }
+        return 1;
+    }
+}
+
+/// Auxiliary function returning the elements of `arr` in order, which the elements of every step
+/// are ranked among.
+///
+/// The elements which are not comparable even to themselves, like `NaN`, go last, which keeps the
+/// order total.
+fn ranked<T>(arr: &[T]) -> Vec<&T>
+    where T: PartialOrd,
+{
+    let mut ranked: Vec<&T> = arr.iter().collect();
+    ranked.sort_by(|a, b| {
+        if NanPolicy::Last.is_less(a, b) {
+            return Ordering::Less;
+        }
+        if NanPolicy::Last.is_less(b, a) {
+            return Ordering::Greater;
+        }
+        return Ordering::Equal;
+    });
+
+    return ranked;
+}
+
+/// Auxiliary function returning the rank of `value` among the `ranked` elements, i.e. the number
+/// of them which go before it in the order of `ranked`.
+fn rank<T>(ranked: &[&T], value: &T) -> usize
+    where T: PartialOrd,
+{
+    return ranked.partition_point(|&other| NanPolicy::Last.is_less(other, value));
+}
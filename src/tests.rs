@@ -1,14 +1,71 @@
+use std::ops::ControlFlow;
+use std::sync::Arc;
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use std::time::{ Duration, Instant };
+use rand::prelude::*;
 use crate::algorithms::{
     self,
+    AbortReason,
+    Algorithm,
+    Bogobogosort,
     Bogosort, 
+    Bozosort,
     BubbleSort,
+    CocktailShakerSort,
+    CombSort,
+    CountingSort,
     GnomeSort,
+    HeapSort,
     InsertionSort,
+    Introsort,
+    IsSorted,
+    LsdRadixSort,
     MergeSort,
+    MsdRadixSort,
+    NanError,
+    NanPolicy,
+    OddEvenSort,
+    Pdqsort,
+    PermutationSort,
+    PivotStrategy,
+    Quicksort,
     SelectionSort,
+    GapSequence,
+    ShellSort,
     Slowsort,
+    SortEvent,
+    SortLimits,
+    SortStats,
     StoogeSort,
+    Timsort,
+    TreeBalance,
+    TreeSort,
 };
+use crate::render::{ self, Color, Highlight, RenderOptions };
+
+#[test]
+fn bogobogosort() {
+    let mut arr_0 = vec![2, 1, 3];
+    let sorted_0  = vec![1, 2, 3];
+
+    let mut arr_1 = vec![1];
+    let sorted_1  = vec![1];
+
+    let mut arr_2 = vec![1.2, 0.1];
+    let sorted_2  = vec![0.1, 1.2];
+
+    assert_eq!(algorithms::bogobogosort(arr_0.clone()), sorted_0);
+    assert_eq!(algorithms::bogobogosort(arr_1.clone()), sorted_1);
+    assert_eq!(algorithms::bogobogosort(arr_2.clone()), sorted_2);
+
+    arr_0.bogobogosort();
+    arr_1.bogobogosort();
+    arr_2.bogobogosort();
+
+    assert_eq!(arr_0, sorted_0);
+    assert_eq!(arr_1, sorted_1);
+    assert_eq!(arr_2, sorted_2);
+}
 
 #[test]
 fn bogosort() {
@@ -34,6 +91,30 @@ fn bogosort() {
     assert_eq!(arr_2, sorted_2);
 }
 
+#[test]
+fn bozosort() {
+    let mut arr_0 = vec![2, 1, 3];
+    let sorted_0  = vec![1, 2, 3];
+
+    let mut arr_1 = vec![1];
+    let sorted_1  = vec![1];
+
+    let mut arr_2 = vec![1.2, 0.1];
+    let sorted_2  = vec![0.1, 1.2];
+
+    assert_eq!(algorithms::bozosort(arr_0.clone()), sorted_0);
+    assert_eq!(algorithms::bozosort(arr_1.clone()), sorted_1);
+    assert_eq!(algorithms::bozosort(arr_2.clone()), sorted_2);
+
+    arr_0.bozosort();
+    arr_1.bozosort();
+    arr_2.bozosort();
+
+    assert_eq!(arr_0, sorted_0);
+    assert_eq!(arr_1, sorted_1);
+    assert_eq!(arr_2, sorted_2);
+}
+
 #[test]
 fn bubble_sort() {
     let mut arr_0 = vec![2, 1, 4, 3];
@@ -58,6 +139,113 @@ fn bubble_sort() {
     assert_eq!(arr_2, sorted_2);
 }
 
+#[test]
+fn cocktail_shaker_sort() {
+    let mut arr_0 = vec![2, 1, 4, 3];
+    let sorted_0  = vec![1, 2, 3, 4];
+
+    let mut arr_1 = vec![1];
+    let sorted_1  = vec![1];
+
+    let mut arr_2 = vec![0.1, 3.1, 2.3, 1.2];
+    let sorted_2  = vec![0.1, 1.2, 2.3, 3.1];
+
+    assert_eq!(algorithms::cocktail_shaker_sort(arr_0.clone()), sorted_0);
+    assert_eq!(algorithms::cocktail_shaker_sort(arr_1.clone()), sorted_1);
+    assert_eq!(algorithms::cocktail_shaker_sort(arr_2.clone()), sorted_2);
+    
+    arr_0.cocktail_shaker_sort();
+    arr_1.cocktail_shaker_sort();
+    arr_2.cocktail_shaker_sort();
+
+    assert_eq!(arr_0, sorted_0);
+    assert_eq!(arr_1, sorted_1);
+    assert_eq!(arr_2, sorted_2);
+}
+
+#[test]
+fn comb_sort() {
+    let mut arr_0 = vec![2, 1, 4, 3];
+    let sorted_0  = vec![1, 2, 3, 4];
+
+    let mut arr_1 = vec![1];
+    let sorted_1  = vec![1];
+
+    let mut arr_2 = vec![0.1, 3.1, 2.3, 1.2];
+    let sorted_2  = vec![0.1, 1.2, 2.3, 3.1];
+
+    assert_eq!(algorithms::comb_sort(arr_0.clone()), sorted_0);
+    assert_eq!(algorithms::comb_sort(arr_1.clone()), sorted_1);
+    assert_eq!(algorithms::comb_sort(arr_2.clone()), sorted_2);
+    
+    let arr_3: Vec<i32> = (0..500).map(|i| (i * 7919) % 257 - 128).collect();
+    let mut sorted_3 = arr_3.clone();
+    sorted_3.sort();
+
+    for shrink in [1.1, 1.3, 2.0, 10.0] {
+        assert_eq!(algorithms::comb_sort_with_shrink(arr_3.clone(), shrink), sorted_3);
+    }
+
+    arr_0.comb_sort();
+    arr_1.comb_sort();
+    arr_2.comb_sort();
+
+    assert_eq!(arr_0, sorted_0);
+    assert_eq!(arr_1, sorted_1);
+    assert_eq!(arr_2, sorted_2);
+}
+
+#[test]
+#[should_panic]
+fn comb_sort_with_invalid_shrink() {
+    algorithms::comb_sort_with_shrink(vec![2, 1], 1.0);
+}
+
+#[test]
+#[should_panic]
+fn comb_sort_with_invalid_shrink_on_empty_vec() {
+    algorithms::comb_sort_with_shrink(Vec::<i32>::new(), 0.5);
+}
+
+#[test]
+#[should_panic]
+fn comb_sort_with_invalid_shrink_steps() {
+    let _ = algorithms::comb_sort_with_shrink_steps(vec![1], 0.5);
+}
+
+#[test]
+fn counting_sort() {
+    let mut arr_0 = vec![2, 1, 4, 3];
+    let sorted_0  = vec![1, 2, 3, 4];
+
+    let mut arr_1 = vec![1];
+    let sorted_1  = vec![1];
+
+    let mut arr_2: Vec<i8> = vec![-3, 127, 0, -128, 5, -3];
+    let sorted_2: Vec<i8>  = vec![-128, -3, -3, 0, 5, 127];
+
+    assert_eq!(algorithms::counting_sort(arr_0.clone()), sorted_0);
+    assert_eq!(algorithms::counting_sort(arr_1.clone()), sorted_1);
+    assert_eq!(algorithms::counting_sort(arr_2.clone()), sorted_2);
+
+    arr_0.counting_sort();
+    arr_1.counting_sort();
+    arr_2.counting_sort();
+
+    assert_eq!(arr_0, sorted_0);
+    assert_eq!(arr_1, sorted_1);
+    assert_eq!(arr_2, sorted_2);
+
+    // Keys too widely spread to count are merge sorted instead of exhausting the memory.
+    assert_eq!(algorithms::counting_sort(vec![i64::MAX, 0, i64::MIN, -1]), [i64::MIN, -1, 0, i64::MAX]);
+    assert_eq!(algorithms::counting_sort(vec![u128::MAX, 1, 0]), [0, 1, u128::MAX]);
+    assert_eq!(algorithms::counting_sort_by_key(vec![(u64::MAX, 0), (0, 1), (u64::MAX, 2)], |p| p.0), [(0, 1), (u64::MAX, 0), (u64::MAX, 2)]);
+
+    let floats = algorithms::counting_sort_with_nans(vec![3.0f32, f32::NAN, -1.0], NanPolicy::Last).unwrap();
+    assert_eq!(floats[..2], [-1.0, 3.0]);
+    assert!(floats[2].is_nan());
+}
+
 #[test]
 fn gnome_sort() {
     let mut arr_0 = vec![2, 1, 4, 3];
@@ -82,6 +270,44 @@ fn gnome_sort() {
     assert_eq!(arr_2, sorted_2);
 }
 
+#[test]
+fn heap_sort() {
+    let mut arr_0 = vec![2, 1, 4, 3];
+    let sorted_0  = vec![1, 2, 3, 4];
+
+    let mut arr_1 = vec![1];
+    let sorted_1  = vec![1];
+
+    let mut arr_2 = vec![0.1, 3.1, 2.3, 1.2];
+    let sorted_2  = vec![0.1, 1.2, 2.3, 3.1];
+
+    assert_eq!(algorithms::heap_sort(arr_0.clone()), sorted_0);
+    assert_eq!(algorithms::heap_sort(arr_1.clone()), sorted_1);
+    assert_eq!(algorithms::heap_sort(arr_2.clone()), sorted_2);
+
+    arr_0.heap_sort();
+    arr_1.heap_sort();
+    arr_2.heap_sort();
+
+    assert_eq!(arr_0, sorted_0);
+    assert_eq!(arr_1, sorted_1);
+    assert_eq!(arr_2, sorted_2);
+
+    // The steps go through building the heap before extracting from it: the first step which is a
+    // max-heap is the end of the heapify phase, and the next one swaps the maximum to the back.
+    let mut arr_3: Vec<i32> = (0..100).collect();
+    arr_3.shuffle(&mut StdRng::seed_from_u64(3));
+    let is_max_heap = |arr: &[i32]| (1..arr.len()).all(|i| arr[(i - 1) / 2] >= arr[i]);
+
+    let (sorted, steps) = algorithms::heap_sort_stepped(arr_3.clone());
+    let heapified = steps.iter().position(|step| is_max_heap(step)).unwrap();
+    assert!(heapified > 0);
+    assert_eq!(steps[heapified + 1].last(), Some(&99));
+    assert!(steps.len() > heapified + 99);
+    assert_eq!(sorted, (0..100).collect::<Vec<i32>>());
+    assert_eq!(steps.last(), Some(&sorted));
+}
+
 #[test]
 fn insertion_sort() {
     let mut arr_0 = vec![2, 1, 4, 3];
@@ -106,6 +332,116 @@ fn insertion_sort() {
     assert_eq!(arr_2, sorted_2);
 }
 
+#[test]
+fn introsort() {
+    let mut arr_0 = vec![2, 1, 4, 3];
+    let sorted_0  = vec![1, 2, 3, 4];
+
+    let mut arr_1 = vec![1];
+    let sorted_1  = vec![1];
+
+    let mut arr_2 = vec![0.1, 3.1, 2.3, 1.2];
+    let sorted_2  = vec![0.1, 1.2, 2.3, 3.1];
+
+    assert_eq!(algorithms::introsort(arr_0.clone()), sorted_0);
+    assert_eq!(algorithms::introsort(arr_1.clone()), sorted_1);
+    assert_eq!(algorithms::introsort(arr_2.clone()), sorted_2);
+
+    // Sorted, reversed, duplicate and organ-pipe inputs are the usual quicksort killers.
+    let inputs: Vec<Vec<i32>> = vec![
+        (0..5000).collect(),
+        (0..5000).rev().collect(),
+        (0..5000).map(|i| i % 7).collect(),
+        (0..2500).chain((0..2500).rev()).collect(),
+        (0..5000).map(|i| (i * 7919) % 5003).collect(),
+    ];
+    for arr_3 in inputs {
+        let mut sorted_3 = arr_3.clone();
+        sorted_3.sort();
+
+        assert_eq!(algorithms::introsort(arr_3.clone()), sorted_3);
+
+        let (sorted, steps) = algorithms::introsort_stepped(arr_3);
+        assert_eq!(sorted, sorted_3);
+        assert_eq!(steps.last(), Some(&sorted_3));
+    }
+
+    arr_0.introsort();
+    arr_1.introsort();
+    arr_2.introsort();
+
+    assert_eq!(arr_0, sorted_0);
+    assert_eq!(arr_1, sorted_1);
+    assert_eq!(arr_2, sorted_2);
+}
+
+#[test]
+fn lsd_radix_sort() {
+    let mut arr_0 = vec![2, 1, 4, 3];
+    let sorted_0  = vec![1, 2, 3, 4];
+
+    let mut arr_1 = vec![1];
+    let sorted_1  = vec![1];
+
+    let mut arr_2 = vec![0.1, 3.1, 2.3, 1.2];
+    let sorted_2  = vec![0.1, 1.2, 2.3, 3.1];
+
+    assert_eq!(algorithms::lsd_radix_sort(arr_0.clone()), sorted_0);
+    assert_eq!(algorithms::lsd_radix_sort(arr_1.clone()), sorted_1);
+    assert_eq!(algorithms::lsd_radix_sort(arr_2.clone()), sorted_2);
+
+    let arr_3: Vec<i64> = (0..1000).map(|i| (i * 7919) % 2003 - 1001).chain([i64::MIN, i64::MAX, 0]).collect();
+    let mut sorted_3 = arr_3.clone();
+    sorted_3.sort();
+
+    let arr_4 = vec![3.5f32, -0.0, f32::INFINITY, -1.5, 0.0, f32::NEG_INFINITY, -1e-30, 1e30];
+    let mut sorted_4 = arr_4.clone();
+    sorted_4.sort_by(f32::total_cmp);
+
+    let arr_5: Vec<u128> = (0..300).map(|i| (i as u128 * 0x9E37_79B9_7F4A_7C15) << (i % 64)).collect();
+    let mut sorted_5 = arr_5.clone();
+    sorted_5.sort();
+
+    assert_eq!(algorithms::lsd_radix_sort(arr_3.clone()), sorted_3);
+    assert_eq!(algorithms::lsd_radix_sort(arr_4.clone()).iter().map(|f| f.to_bits()).collect::<Vec<_>>(),
+               sorted_4.iter().map(|f| f.to_bits()).collect::<Vec<_>>());
+    assert_eq!(algorithms::lsd_radix_sort(arr_5.clone()), sorted_5);
+
+    for radix in [2, 16, 1 << 8, 1 << 16] {
+        assert_eq!(algorithms::lsd_radix_sort_with_radix(arr_3.clone(), radix), sorted_3);
+    }
+
+    let (sorted, steps) = algorithms::lsd_radix_sort_stepped(arr_2.clone());
+    assert_eq!(sorted, sorted_2);
+    assert_eq!(steps.last(), Some(&sorted_2));
+
+    arr_0.lsd_radix_sort();
+    arr_1.lsd_radix_sort();
+    arr_2.lsd_radix_sort();
+
+    assert_eq!(arr_0, sorted_0);
+    assert_eq!(arr_1, sorted_1);
+    assert_eq!(arr_2, sorted_2);
+}
+
+#[test]
+#[should_panic]
+fn lsd_radix_sort_with_invalid_radix() {
+    algorithms::lsd_radix_sort_with_radix(vec![3, 1, 2], 3);
+}
+
+#[test]
+#[should_panic]
+fn lsd_radix_sort_with_invalid_radix_on_empty_vec() {
+    algorithms::lsd_radix_sort_with_radix(Vec::<u32>::new(), 3);
+}
+
+#[test]
+#[should_panic]
+fn lsd_radix_sort_with_invalid_radix_on_single_element() {
+    algorithms::lsd_radix_sort_with_radix(vec![1], 3);
+}
+
 #[test]
 fn merge_sort() {
     let mut arr_0 = vec![2, 1, 4, 3];
@@ -130,9 +466,187 @@ fn merge_sort() {
     assert_eq!(arr_2, sorted_2);
 }
 
+#[test]
+fn msd_radix_sort() {
+    let mut arr_0 = vec![2, 1, 4, 3];
+    let sorted_0  = vec![1, 2, 3, 4];
+
+    let mut arr_1 = vec![1];
+    let sorted_1  = vec![1];
+
+    let mut arr_2 = vec![0.1, 3.1, 2.3, 1.2];
+    let sorted_2  = vec![0.1, 1.2, 2.3, 3.1];
+
+    assert_eq!(algorithms::msd_radix_sort(arr_0.clone()), sorted_0);
+    assert_eq!(algorithms::msd_radix_sort(arr_1.clone()), sorted_1);
+    assert_eq!(algorithms::msd_radix_sort(arr_2.clone()), sorted_2);
+
+    let arr_3: Vec<i64> = (0..1000).map(|i| (i * 7919) % 2003 - 1001).chain([i64::MIN, i64::MAX, 0]).collect();
+    let mut sorted_3 = arr_3.clone();
+    sorted_3.sort();
+
+    let arr_4 = vec![3.5f32, -0.0, f32::INFINITY, -1.5, 0.0, f32::NEG_INFINITY, -1e-30, 1e30];
+    let mut sorted_4 = arr_4.clone();
+    sorted_4.sort_by(f32::total_cmp);
+
+    let arr_5: Vec<u128> = (0..300).map(|i| (i as u128 * 0x9E37_79B9_7F4A_7C15) << (i % 64)).collect();
+    let mut sorted_5 = arr_5.clone();
+    sorted_5.sort();
+
+    assert_eq!(algorithms::msd_radix_sort(arr_3.clone()), sorted_3);
+    assert_eq!(algorithms::msd_radix_sort(arr_4.clone()).iter().map(|f| f.to_bits()).collect::<Vec<_>>(),
+               sorted_4.iter().map(|f| f.to_bits()).collect::<Vec<_>>());
+    assert_eq!(algorithms::msd_radix_sort(arr_5.clone()), sorted_5);
+
+    arr_0.msd_radix_sort();
+    arr_1.msd_radix_sort();
+    arr_2.msd_radix_sort();
+
+    assert_eq!(arr_0, sorted_0);
+    assert_eq!(arr_1, sorted_1);
+    assert_eq!(arr_2, sorted_2);
+}
+
+#[test]
+fn odd_even_sort() {
+    let mut arr_0 = vec![2, 1, 4, 3];
+    let sorted_0  = vec![1, 2, 3, 4];
+
+    let mut arr_1 = vec![1];
+    let sorted_1  = vec![1];
+
+    let mut arr_2 = vec![0.1, 3.1, 2.3, 1.2];
+    let sorted_2  = vec![0.1, 1.2, 2.3, 3.1];
+
+    assert_eq!(algorithms::odd_even_sort(arr_0.clone()), sorted_0);
+    assert_eq!(algorithms::odd_even_sort(arr_1.clone()), sorted_1);
+    assert_eq!(algorithms::odd_even_sort(arr_2.clone()), sorted_2);
+    
+    arr_0.odd_even_sort();
+    arr_1.odd_even_sort();
+    arr_2.odd_even_sort();
+
+    assert_eq!(arr_0, sorted_0);
+    assert_eq!(arr_1, sorted_1);
+    assert_eq!(arr_2, sorted_2);
+}
+
+#[test]
+fn pdqsort() {
+    let mut arr_0 = vec![2, 1, 4, 3];
+    let sorted_0  = vec![1, 2, 3, 4];
+
+    let mut arr_1 = vec![1];
+    let sorted_1  = vec![1];
+
+    let mut arr_2 = vec![0.1, 3.1, 2.3, 1.2];
+    let sorted_2  = vec![0.1, 1.2, 2.3, 3.1];
+
+    assert_eq!(algorithms::pdqsort(arr_0.clone()), sorted_0);
+    assert_eq!(algorithms::pdqsort(arr_1.clone()), sorted_1);
+    assert_eq!(algorithms::pdqsort(arr_2.clone()), sorted_2);
+
+    // Sorted, reversed, duplicate and organ-pipe inputs exercise the pattern detection.
+    let inputs: Vec<Vec<i32>> = vec![
+        (0..5000).collect(),
+        (0..5000).rev().collect(),
+        (0..5000).map(|i| i % 7).collect(),
+        (0..2500).chain((0..2500).rev()).collect(),
+        (0..5000).map(|i| (i * 7919) % 5003).collect(),
+    ];
+    for arr_3 in inputs {
+        let mut sorted_3 = arr_3.clone();
+        sorted_3.sort();
+
+        assert_eq!(algorithms::pdqsort(arr_3.clone()), sorted_3);
+
+        let (sorted, steps) = algorithms::pdqsort_stepped(arr_3);
+        assert_eq!(sorted, sorted_3);
+        assert_eq!(steps.last(), Some(&sorted_3));
+    }
+
+    arr_0.pdqsort();
+    arr_1.pdqsort();
+    arr_2.pdqsort();
+
+    assert_eq!(arr_0, sorted_0);
+    assert_eq!(arr_1, sorted_1);
+    assert_eq!(arr_2, sorted_2);
+}
+
+#[test]
+fn permutation_sort() {
+    let mut arr_0 = vec![2, 1, 4, 3];
+    let sorted_0  = vec![1, 2, 3, 4];
+
+    let mut arr_1 = vec![1];
+    let sorted_1  = vec![1];
+
+    let mut arr_2 = vec![1.2, 0.1];
+    let sorted_2  = vec![0.1, 1.2];
+
+    assert_eq!(algorithms::permutation_sort(arr_0.clone()), sorted_0);
+    assert_eq!(algorithms::permutation_sort(arr_1.clone()), sorted_1);
+    assert_eq!(algorithms::permutation_sort(arr_2.clone()), sorted_2);
+
+    // The sorted permutation is the first one, so the last one wraps around to it right away,
+    // while the second one has to go through all of the others.
+    assert_eq!([3, 2, 1].permutation_sort_stepped(), [[3, 2, 1], [1, 2, 3]]);
+    assert_eq!([1, 3, 2].permutation_sort_stepped().len(), 6);
+    assert_eq!(algorithms::permutation_sort(vec![2, 1, 2, 1, 3]), [1, 1, 2, 2, 3]);
+
+    arr_0.permutation_sort();
+    arr_1.permutation_sort();
+    arr_2.permutation_sort();
+
+    assert_eq!(arr_0, sorted_0);
+    assert_eq!(arr_1, sorted_1);
+    assert_eq!(arr_2, sorted_2);
+}
+
 #[test]
 fn quicksort() {
-    todo!()
+    let mut arr_0 = vec![2, 1, 4, 3];
+    let sorted_0  = vec![1, 2, 3, 4];
+
+    let mut arr_1 = vec![1];
+    let sorted_1  = vec![1];
+
+    let mut arr_2 = vec![0.1, 3.1, 2.3, 1.2];
+    let sorted_2  = vec![0.1, 1.2, 2.3, 3.1];
+
+    assert_eq!(algorithms::quicksort(arr_0.clone()), sorted_0);
+    assert_eq!(algorithms::quicksort(arr_1.clone()), sorted_1);
+    assert_eq!(algorithms::quicksort(arr_2.clone()), sorted_2);
+
+    for pivot in [PivotStrategy::First, PivotStrategy::Last, PivotStrategy::Random, PivotStrategy::MedianOfThree] {
+        let arr_3 = vec![5, 3, 3, 9, 0, 1, 8, 3, 7];
+        let sorted_3 = vec![0, 1, 3, 3, 3, 5, 7, 8, 9];
+
+        assert_eq!(algorithms::quicksort_with_pivot(arr_3.clone(), pivot), sorted_3);
+
+        let (sorted, steps) = algorithms::quicksort_with_pivot_stepped(arr_3.clone(), pivot);
+        assert_eq!(sorted, sorted_3);
+        assert_eq!(steps.first(), Some(&arr_3));
+        assert_eq!(steps.last(), Some(&sorted_3));
+    }
+
+    // The elements equal to the pivot are kept out of both partitions, so equal elements take a
+    // single pass instead of a quadratic number of comparisons.
+    let (sorted, stats) = algorithms::quicksort_counted(vec![7; 1000]);
+    assert_eq!(sorted, vec![7; 1000]);
+    assert!(stats.comparisons < 3 * 1000);
+
+    let (_, stats) = algorithms::quicksort_counted((0..1000).map(|i| i % 3).collect());
+    assert!(stats.comparisons < 10 * 1000);
+
+    arr_0.quicksort();
+    arr_1.quicksort();
+    arr_2.quicksort();
+
+    assert_eq!(arr_0, sorted_0);
+    assert_eq!(arr_1, sorted_1);
+    assert_eq!(arr_2, sorted_2);
 }
 
 #[test]
@@ -150,6 +664,11 @@ fn selection_sort() {
     assert_eq!(algorithms::selection_sort(arr_1.clone()), sorted_1);
     assert_eq!(algorithms::selection_sort(arr_2.clone()), sorted_2);
 
+    // Sorting in place must record the same steps as sorting a copy.
+    let arr_3 = vec![3, 1, 2, 1, 0];
+    let (_, steps_3) = algorithms::selection_sort_stepped(arr_3.clone());
+    assert_eq!(arr_3.clone().selection_sort_stepped(), steps_3);
+
     arr_0.selection_sort();
     arr_1.selection_sort();
     arr_2.selection_sort();
@@ -159,6 +678,55 @@ fn selection_sort() {
     assert_eq!(arr_2, sorted_2);
 }
 
+#[test]
+fn shell_sort() {
+    let mut arr_0 = vec![2, 1, 4, 3];
+    let sorted_0  = vec![1, 2, 3, 4];
+
+    let mut arr_1 = vec![1];
+    let sorted_1  = vec![1];
+
+    let mut arr_2 = vec![0.1, 3.1, 2.3, 1.2];
+    let sorted_2  = vec![0.1, 1.2, 2.3, 3.1];
+
+    assert_eq!(algorithms::shell_sort(arr_0.clone()), sorted_0);
+    assert_eq!(algorithms::shell_sort(arr_1.clone()), sorted_1);
+    assert_eq!(algorithms::shell_sort(arr_2.clone()), sorted_2);
+
+    let arr_3: Vec<i32> = (0..2000).map(|i| (i * 7919) % 1009 - 500).collect();
+    let mut sorted_3 = arr_3.clone();
+    sorted_3.sort();
+
+    let custom = [5, 0, 3, 5000];
+    let sequences = [
+        GapSequence::Shell,
+        GapSequence::Knuth,
+        GapSequence::Sedgewick,
+        GapSequence::Ciura,
+        GapSequence::Tokuda,
+        GapSequence::Custom(&custom),
+    ];
+    for gaps in sequences {
+        assert_eq!(gaps.gaps(arr_3.len()).last(), Some(&1));
+        assert_eq!(algorithms::shell_sort_with_gaps(arr_3.clone(), gaps), sorted_3);
+    }
+
+    assert_eq!(GapSequence::Shell.gaps(20), vec![10, 5, 2, 1]);
+    assert_eq!(GapSequence::Knuth.gaps(100), vec![40, 13, 4, 1]);
+    assert_eq!(GapSequence::Sedgewick.gaps(300), vec![281, 77, 23, 8, 1]);
+    assert_eq!(GapSequence::Ciura.gaps(2000), vec![1577, 701, 301, 132, 57, 23, 10, 4, 1]);
+    assert_eq!(GapSequence::Tokuda.gaps(200), vec![103, 46, 20, 9, 4, 1]);
+    assert_eq!(GapSequence::Custom(&custom).gaps(10), vec![5, 3, 1]);
+
+    arr_0.shell_sort();
+    arr_1.shell_sort();
+    arr_2.shell_sort();
+
+    assert_eq!(arr_0, sorted_0);
+    assert_eq!(arr_1, sorted_1);
+    assert_eq!(arr_2, sorted_2);
+}
+
 #[test]
 fn slowsort() {
     let mut arr_0 = vec![2, 1, 4, 3];
@@ -207,7 +775,851 @@ fn stooge_sort() {
     assert_eq!(arr_2, sorted_2);
 }
 
+#[test]
+fn timsort() {
+    let mut arr_0 = vec![2, 1, 4, 3];
+    let sorted_0  = vec![1, 2, 3, 4];
+
+    let mut arr_1 = vec![1];
+    let sorted_1  = vec![1];
+
+    let mut arr_2 = vec![0.1, 3.1, 2.3, 1.2];
+    let sorted_2  = vec![0.1, 1.2, 2.3, 3.1];
+
+    assert_eq!(algorithms::timsort(arr_0.clone()), sorted_0);
+    assert_eq!(algorithms::timsort(arr_1.clone()), sorted_1);
+    assert_eq!(algorithms::timsort(arr_2.clone()), sorted_2);
+
+    // Long presorted, reversed and equal runs exercise the merges and the galloping.
+    let mut arr_3: Vec<i32> = (0..3000).collect();
+    arr_3.extend((0..2000).rev());
+    arr_3.extend([7; 500]);
+    arr_3.extend((0..4000).map(|i| (i * 7919) % 4001));
+    arr_3.extend((1000..1500).rev());
+    let mut sorted_3 = arr_3.clone();
+    sorted_3.sort();
+
+    assert_eq!(algorithms::timsort(arr_3.clone()), sorted_3);
+
+    let (sorted, steps) = algorithms::timsort_stepped(arr_3.clone());
+    assert_eq!(sorted, sorted_3);
+    assert_eq!(steps.last(), Some(&sorted_3));
+
+    arr_0.timsort();
+    arr_1.timsort();
+    arr_2.timsort();
+
+    assert_eq!(arr_0, sorted_0);
+    assert_eq!(arr_1, sorted_1);
+    assert_eq!(arr_2, sorted_2);
+}
+
 #[test]
 fn tree_sort() {
-    unimplemented!()
+    let mut arr_0 = vec![2, 1, 4, 3];
+    let sorted_0  = vec![1, 2, 3, 4];
+
+    let mut arr_1 = vec![1];
+    let sorted_1  = vec![1];
+
+    let mut arr_2 = vec![0.1, 3.1, 2.3, 1.2];
+    let sorted_2  = vec![0.1, 1.2, 2.3, 3.1];
+
+    assert_eq!(algorithms::tree_sort(arr_0.clone()), sorted_0);
+    assert_eq!(algorithms::tree_sort(arr_1.clone()), sorted_1);
+    assert_eq!(algorithms::tree_sort(arr_2.clone()), sorted_2);
+
+    // Sorted input is the worst case of the unbalanced tree.
+    let arr_3: Vec<u32> = (0..1000).rev().chain(0..1000).collect();
+    let mut sorted_3 = arr_3.clone();
+    sorted_3.sort();
+
+    for balance in [TreeBalance::Unbalanced, TreeBalance::Avl] {
+        assert_eq!(algorithms::tree_sort_with_balance(arr_3.clone(), balance), sorted_3);
+
+        let (sorted, steps) = algorithms::tree_sort_with_balance_stepped(arr_2.clone(), balance);
+        assert_eq!(sorted, sorted_2);
+        // One step per insertion, then a single swap of 3.1 and 1.2 puts the array in order.
+        assert_eq!(steps.len(), 1 + arr_2.len() + 1);
+        assert_eq!(steps[arr_2.len()].0, sorted_2);
+        assert_eq!(steps.last(), Some(&(sorted_2.clone(), sorted_2.clone())));
+    }
+
+    arr_0.tree_sort();
+    arr_1.tree_sort();
+    arr_2.tree_sort();
+
+    assert_eq!(arr_0, sorted_0);
+    assert_eq!(arr_1, sorted_1);
+    assert_eq!(arr_2, sorted_2);
+}
+
+#[test]
+fn slices() {
+    // Arrays, sub-slices and boxed slices are sorted in place through the trait implementations.
+    let mut arr_0 = [4, 3, 2, 1];
+    arr_0.quicksort();
+    assert_eq!(arr_0, [1, 2, 3, 4]);
+
+    let mut arr_1 = vec![9, 5, 4, 3, 2, 0];
+    arr_1[1..5].merge_sort();
+    assert_eq!(arr_1, vec![9, 2, 3, 4, 5, 0]);
+
+    arr_1[..3].selection_sort();
+    assert_eq!(arr_1, vec![2, 3, 9, 4, 5, 0]);
+
+    let steps = arr_1[3..].stooge_sort_stepped();
+    assert_eq!(arr_1, vec![2, 3, 9, 0, 4, 5]);
+    assert_eq!(steps.last(), Some(&vec![0, 4, 5]));
+
+    let mut arr_2: Box<[f64]> = vec![0.3, 0.1, 0.2].into_boxed_slice();
+    arr_2.slowsort();
+    assert_eq!(&arr_2[..], &[0.1, 0.2, 0.3]);
+
+    let arr_3: &mut [u8] = &mut [];
+    arr_3.timsort();
+    assert!(arr_3.is_empty());
+}
+
+#[test]
+fn non_copy() {
+    // Neither `Copy` nor `Clone`, so only moves and swaps can sort it.
+    #[derive(Debug, PartialEq, PartialOrd)]
+    struct Record(u32, String);
+
+    let records = || vec![
+        Record(3, "c".to_string()),
+        Record(1, "a".to_string()),
+        Record(4, "d".to_string()),
+        Record(2, "b".to_string()),
+    ];
+    let sorted = vec![
+        Record(1, "a".to_string()),
+        Record(2, "b".to_string()),
+        Record(3, "c".to_string()),
+        Record(4, "d".to_string()),
+    ];
+
+    let sorts: Vec<fn(&mut [Record])> = vec![
+        |arr| arr.bogobogosort(),
+        |arr| arr.bogosort(),
+        |arr| arr.bozosort(),
+        |arr| arr.bubble_sort(),
+        |arr| arr.cocktail_shaker_sort(),
+        |arr| arr.comb_sort(),
+        |arr| arr.gnome_sort(),
+        |arr| arr.heap_sort(),
+        |arr| arr.insertion_sort(),
+        |arr| arr.introsort(),
+        |arr| arr.merge_sort(),
+        |arr| arr.odd_even_sort(),
+        |arr| arr.pdqsort(),
+        |arr| arr.permutation_sort(),
+        |arr| arr.quicksort(),
+        |arr| arr.selection_sort(),
+        |arr| arr.shell_sort(),
+        |arr| arr.slowsort(),
+        |arr| arr.stooge_sort(),
+        |arr| arr.timsort(),
+        |arr| arr.tree_sort_with_balance(TreeBalance::Avl),
+    ];
+    for sort in sorts {
+        let mut arr = records();
+        sort(&mut arr);
+        assert_eq!(arr, sorted);
+    }
+
+    assert_eq!(algorithms::merge_sort(records()), sorted);
+    assert_eq!(algorithms::selection_sort(records()), sorted);
+
+    // The stepped variants only need `Clone`.
+    let words: Vec<String> = ["pear", "fig", "apple", "kiwi", "fig"].iter().map(|w| w.to_string()).collect();
+    let mut sorted_words = words.clone();
+    sorted_words.sort();
+
+    let (sorted, steps) = algorithms::timsort_stepped(words.clone());
+    assert_eq!(sorted, sorted_words);
+    assert_eq!(steps.last(), Some(&sorted_words));
+
+    let (sorted, steps) = algorithms::tree_sort_stepped(words.clone());
+    assert_eq!(sorted, sorted_words);
+    assert_eq!(steps.last(), Some(&(sorted_words.clone(), sorted_words.clone())));
+
+    let mut arr = words;
+    let steps = arr.merge_sort_stepped();
+    assert_eq!(arr, sorted_words);
+    assert_eq!(steps.last(), Some(&sorted_words));
+}
+
+#[test]
+fn by_and_by_key() {
+    let arr = vec![3, 9, -4, 0, 12, 3, -7, 5];
+    let reversed = vec![12, 9, 5, 3, 3, 0, -4, -7];
+
+    let sorts: Vec<fn(&mut [i32])> = vec![
+        |arr| arr.bubble_sort_by(|a, b| b.cmp(a)),
+        |arr| arr.cocktail_shaker_sort_by(|a, b| b.cmp(a)),
+        |arr| arr.comb_sort_by(|a, b| b.cmp(a)),
+        |arr| arr.gnome_sort_by(|a, b| b.cmp(a)),
+        |arr| arr.heap_sort_by(|a, b| b.cmp(a)),
+        |arr| arr.insertion_sort_by(|a, b| b.cmp(a)),
+        |arr| arr.introsort_by(|a, b| b.cmp(a)),
+        |arr| arr.merge_sort_by(|a, b| b.cmp(a)),
+        |arr| arr.odd_even_sort_by(|a, b| b.cmp(a)),
+        |arr| arr.pdqsort_by(|a, b| b.cmp(a)),
+        |arr| arr.quicksort_by(|a, b| b.cmp(a)),
+        |arr| arr.selection_sort_by(|a, b| b.cmp(a)),
+        |arr| arr.shell_sort_by(|a, b| b.cmp(a)),
+        |arr| arr.slowsort_by(|a, b| b.cmp(a)),
+        |arr| arr.stooge_sort_by(|a, b| b.cmp(a)),
+        |arr| arr.timsort_by(|a, b| b.cmp(a)),
+        |arr| arr.tree_sort_by(|a, b| b.cmp(a)),
+        |arr| arr.counting_sort_by_key(|x| -x),
+        |arr| arr.lsd_radix_sort_by_key(|x| -x),
+        |arr| arr.msd_radix_sort_by_key(|x| -x),
+    ];
+    for sort in sorts {
+        let mut arr = arr.clone();
+        sort(&mut arr);
+        assert_eq!(arr, reversed);
+    }
+
+    let mut arr_0 = vec![0.5, -1.0, 2.0];
+    arr_0.bogosort_by(|a: &f64, b| b.total_cmp(a));
+    assert_eq!(arr_0, [2.0, 0.5, -1.0]);
+
+    // The stable sorts keep the people of the same age in their original order.
+    let people = || vec![("Ada", 36), ("Bob", 25), ("Cy", 36), ("Di", 25), ("Ed", 19)];
+    let by_age = vec![("Ed", 19), ("Bob", 25), ("Di", 25), ("Ada", 36), ("Cy", 36)];
+
+    assert_eq!(algorithms::merge_sort_by_key(people(), |p| p.1), by_age);
+    assert_eq!(algorithms::timsort_by_key(people(), |p| p.1), by_age);
+    assert_eq!(algorithms::insertion_sort_by_key(people(), |p| p.1), by_age);
+    assert_eq!(algorithms::tree_sort_by_key(people(), |p| p.1), by_age);
+    assert_eq!(algorithms::counting_sort_by_key(people(), |p| p.1), by_age);
+    assert_eq!(algorithms::lsd_radix_sort_by_key(people(), |p| p.1), by_age);
+    assert_eq!(algorithms::msd_radix_sort_by_key(people(), |p| p.1), by_age);
+
+    let sorted = algorithms::heap_sort_by_key(people(), |p| p.1);
+    assert_eq!(sorted.iter().map(|p| p.1).collect::<Vec<_>>(), [19, 25, 25, 36, 36]);
+
+    // Keys and comparators work on types which can't be sorted themselves.
+    let mut words = vec!["Pear".to_string(), "fig".to_string(), "apple".to_string(), "Kiwi".to_string()];
+    words.quicksort_by_key(|w| w.to_lowercase());
+    assert_eq!(words, ["apple", "fig", "Kiwi", "Pear"]);
+
+    words.lsd_radix_sort_by_key(|w| w.len());
+    assert_eq!(words, ["fig", "Kiwi", "Pear", "apple"]);
+
+    let (sorted, steps) = algorithms::selection_sort_by_stepped(vec![1, 3, 2], |a, b| b.cmp(a));
+    assert_eq!(sorted, [3, 2, 1]);
+    assert_eq!(steps.last(), Some(&(vec![], vec![3, 2, 1])));
+
+    let (sorted, steps) = algorithms::msd_radix_sort_by_key_stepped(people(), |p| p.1);
+    assert_eq!(sorted, by_age);
+    assert_eq!(steps.first(), Some(&people()));
+    assert_eq!(steps.last(), Some(&by_age));
+}
+
+#[test]
+fn by_cached_key() {
+    use std::cell::Cell;
+
+    let dates = || vec!["2021-03-04", "1999-12-31", "2021-01-15", "1999-12-31", "2000-06-01"];
+    let sorted = vec!["1999-12-31", "1999-12-31", "2000-06-01", "2021-01-15", "2021-03-04"];
+
+    // Every key is only computed once per element.
+    let calls = Cell::new(0);
+    let parse = |date: &&str| {
+        calls.set(calls.get() + 1);
+        date.split('-').map(|part| part.parse::<u32>().unwrap()).collect::<Vec<_>>()
+    };
+
+    assert_eq!(algorithms::bubble_sort_by_cached_key(dates(), parse), sorted);
+    assert_eq!(calls.get(), 5);
+
+    let mut arr = dates();
+    arr.heap_sort_by_cached_key(parse);
+    assert_eq!(arr, sorted);
+    assert_eq!(calls.get(), 10);
+
+    type Sort = fn(&mut [(char, i32)]);
+    let sorts: Vec<Sort> = vec![
+        |arr| arr.bogosort_by_cached_key(|p| p.1.abs()),
+        |arr| arr.cocktail_shaker_sort_by_cached_key(|p| p.1.abs()),
+        |arr| arr.comb_sort_by_cached_key(|p| p.1.abs()),
+        |arr| arr.gnome_sort_by_cached_key(|p| p.1.abs()),
+        |arr| arr.insertion_sort_by_cached_key(|p| p.1.abs()),
+        |arr| arr.introsort_by_cached_key(|p| p.1.abs()),
+        |arr| arr.merge_sort_by_cached_key(|p| p.1.abs()),
+        |arr| arr.odd_even_sort_by_cached_key(|p| p.1.abs()),
+        |arr| arr.pdqsort_by_cached_key(|p| p.1.abs()),
+        |arr| arr.quicksort_by_cached_key(|p| p.1.abs()),
+        |arr| arr.selection_sort_by_cached_key(|p| p.1.abs()),
+        |arr| arr.shell_sort_by_cached_key(|p| p.1.abs()),
+        |arr| arr.slowsort_by_cached_key(|p| p.1.abs()),
+        |arr| arr.stooge_sort_by_cached_key(|p| p.1.abs()),
+        |arr| arr.timsort_by_cached_key(|p| p.1.abs()),
+        |arr| arr.tree_sort_by_cached_key(|p| p.1.abs()),
+    ];
+    for sort in sorts {
+        // Sorting the (key, index) pairs keeps even the unstable sorts stable.
+        let mut arr = vec![('a', -3), ('b', 2), ('c', 3), ('d', -2), ('e', 0)];
+        sort(&mut arr);
+        assert_eq!(arr, [('e', 0), ('b', 2), ('d', -2), ('a', -3), ('c', 3)]);
+    }
+
+    // The steps show the elements themselves rather than the keys.
+    let (sorted, steps) = algorithms::quicksort_by_cached_key_stepped(vec!["ccc", "a", "bb"], |s| s.len());
+    assert_eq!(sorted, ["a", "bb", "ccc"]);
+    assert_eq!(steps.first(), Some(&vec!["ccc", "a", "bb"]));
+    assert_eq!(steps.last(), Some(&vec!["a", "bb", "ccc"]));
+
+    let mut arr = ["ccc", "a", "bb"];
+    let steps = arr.selection_sort_by_cached_key_stepped(|s| s.len());
+    assert_eq!(steps.last(), Some(&(vec![], vec!["a", "bb", "ccc"])));
+
+    let steps = algorithms::tree_sort_by_cached_key_stepped(vec!["ccc", "a", "bb"], |s| s.len()).1;
+    assert_eq!(steps[3], (vec!["a", "bb", "ccc"], vec!["ccc", "a", "bb"]));
+    assert_eq!(steps.last(), Some(&(vec!["a", "bb", "ccc"], vec!["a", "bb", "ccc"])));
+}
+
+#[test]
+fn nan_policy() {
+    let nan = f64::NAN;
+    let arr = vec![2.0, nan, -1.0, 0.5, nan, 3.0];
+
+    let sorts: Vec<fn(&mut [f64], NanPolicy) -> Result<(), NanError>> = vec![
+        |arr, policy| arr.bubble_sort_with_nans(policy),
+        |arr, policy| arr.cocktail_shaker_sort_with_nans(policy),
+        |arr, policy| arr.comb_sort_with_nans(policy),
+        |arr, policy| arr.gnome_sort_with_nans(policy),
+        |arr, policy| arr.heap_sort_with_nans(policy),
+        |arr, policy| arr.insertion_sort_with_nans(policy),
+        |arr, policy| arr.introsort_with_nans(policy),
+        |arr, policy| arr.merge_sort_with_nans(policy),
+        |arr, policy| arr.odd_even_sort_with_nans(policy),
+        |arr, policy| arr.pdqsort_with_nans(policy),
+        |arr, policy| arr.quicksort_with_nans(policy),
+        |arr, policy| arr.selection_sort_with_nans(policy),
+        |arr, policy| arr.shell_sort_with_nans(policy),
+        |arr, policy| arr.slowsort_with_nans(policy),
+        |arr, policy| arr.stooge_sort_with_nans(policy),
+        |arr, policy| arr.timsort_with_nans(policy),
+        |arr, policy| arr.tree_sort_with_nans(policy),
+        |arr, policy| arr.lsd_radix_sort_with_nans(policy),
+        |arr, policy| arr.msd_radix_sort_with_nans(policy),
+    ];
+    for sort in sorts {
+        let mut arr_0 = arr.clone();
+        assert_eq!(sort(&mut arr_0, NanPolicy::First), Ok(()));
+        assert!(arr_0[..2].iter().all(|x| x.is_nan()));
+        assert_eq!(arr_0[2..], [-1.0, 0.5, 2.0, 3.0]);
+        assert!(arr_0.is_sorted_with_nans(NanPolicy::First));
+
+        let mut arr_1 = arr.clone();
+        assert_eq!(sort(&mut arr_1, NanPolicy::Last), Ok(()));
+        assert_eq!(arr_1[..4], [-1.0, 0.5, 2.0, 3.0]);
+        assert!(arr_1[4..].iter().all(|x| x.is_nan()));
+
+        // Under the error policy, the slice is left as it is.
+        let mut arr_2 = arr.clone();
+        assert_eq!(sort(&mut arr_2, NanPolicy::Error), Err(NanError { index: 1 }));
+        assert_eq!(arr_2[0], 2.0);
+        assert!(arr_2[1].is_nan());
+
+        let mut arr_3 = vec![2.0, -1.0, 0.5];
+        assert_eq!(sort(&mut arr_3, NanPolicy::Error), Ok(()));
+        assert_eq!(arr_3, [-1.0, 0.5, 2.0]);
+    }
+
+    let (sorted, steps) = algorithms::quicksort_with_nans_stepped(vec![nan, 1.0, 0.0], NanPolicy::Last).unwrap();
+    assert_eq!(sorted[..2], [0.0, 1.0]);
+    assert!(steps.last().unwrap()[2].is_nan());
+    assert_eq!(algorithms::counting_sort_with_nans(vec![3u8, 1, 2], NanPolicy::Error), Ok(vec![1, 2, 3]));
+
+    let mut arr_4 = [1.0, -nan, -0.0, f64::INFINITY, nan, 0.0, -2.0];
+    arr_4.heap_sort_total();
+    let bits: Vec<u64> = arr_4.iter().map(|x| x.to_bits()).collect();
+    let expected: Vec<u64> = [-nan, -2.0, -0.0, 0.0, 1.0, f64::INFINITY, nan].iter().map(|x| x.to_bits()).collect();
+    assert_eq!(bits, expected);
+    assert_eq!(algorithms::timsort_total(vec![0.0f32, -0.0]), [-0.0, 0.0]);
+
+    // The inherent `is_sorted` of slices shadows the one of the trait, but not on `Vec`s.
+    assert!(IsSorted::is_sorted(&[1.0, 2.0][..]));
+    assert!(!IsSorted::is_sorted(&[1.0, nan, 2.0][..]));
+    assert!(!vec![2.0, nan, 1.0].is_sorted());
+    assert!(!arr.is_sorted_with_nans(NanPolicy::Error));
+
+    // Sorting with a `NaN` policy leaves the slice sorted under that policy, which it isn't as far
+    // as the partial order is concerned.
+    let nans_last = algorithms::merge_sort_with_nans(vec![nan, 1.0, 0.0], NanPolicy::Last).unwrap();
+    assert!(nans_last.is_sorted_with_nans(NanPolicy::Last));
+    assert!(!nans_last.is_sorted_with_nans(NanPolicy::First));
+    assert!(!nans_last[..].is_sorted());
+    assert!(!IsSorted::is_sorted(&nans_last[..]));
+
+    // Inconsistent comparisons leave the order unspecified, but the sorts still terminate.
+    let sorts: Vec<fn(&mut [i32])> = vec![
+        |arr| arr.bubble_sort_by(|_, _| std::cmp::Ordering::Less),
+        |arr| arr.comb_sort_by(|_, _| std::cmp::Ordering::Less),
+        |arr| arr.gnome_sort_by(|_, _| std::cmp::Ordering::Less),
+        |arr| arr.odd_even_sort_by(|_, _| std::cmp::Ordering::Less),
+    ];
+    for sort in sorts {
+        let mut arr = vec![5, 3, 1, 4, 2];
+        sort(&mut arr);
+        arr.sort();
+        assert_eq!(arr, [1, 2, 3, 4, 5]);
+    }
+}
+
+#[test]
+fn sorter() {
+    let arr = vec![5, -3, 8, 0, -3, 2];
+    let sorted = vec![-3, -3, 0, 2, 5, 8];
+
+    // Bogobogosort would take billions of shuffles on this many elements.
+    for algorithm in Algorithm::ALL.into_iter().filter(|&algorithm| algorithm != Algorithm::Bogobogosort) {
+        let sorter = algorithm.numeric_sorter();
+        assert_eq!(sorter.algorithm(), algorithm);
+
+        let mut arr_0 = arr.clone();
+        sorter.sort(&mut arr_0);
+        assert_eq!(arr_0, sorted, "{}", algorithm);
+
+        let mut arr_1 = arr.clone();
+        let (steps, _) = sorter.sort_stepped_and_timed(&mut arr_1);
+        assert_eq!(arr_1, sorted, "{}", algorithm);
+        assert_eq!(steps.first(), Some(&arr), "{}", algorithm);
+        assert_eq!(steps.last(), Some(&sorted), "{}", algorithm);
+
+        assert_eq!(algorithm.to_string().parse(), Ok(algorithm));
+        assert_eq!(algorithm.sorter::<String>().is_some(), algorithm.is_comparison_sort());
+    }
+
+    let mut arr_2 = vec!["pear".to_string(), "apple".to_string(), "fig".to_string()];
+    let sorter = "Gnome Sort".parse::<Algorithm>().unwrap().sorter().unwrap();
+    let steps = sorter.sort_stepped(&mut arr_2);
+    assert_eq!(arr_2, ["apple", "fig", "pear"]);
+    assert_eq!(steps.len(), 3);
+
+    assert_eq!(Algorithm::SelectionSort.to_string(), "selection_sort");
+    assert_eq!("odd-even-sort".parse(), Ok(Algorithm::OddEvenSort));
+    assert!("sleep sort".parse::<Algorithm>().is_err());
+}
+
+#[test]
+fn catalog() {
+    assert_eq!(algorithms::catalog().len(), Algorithm::ALL.len());
+
+    for (info, algorithm) in algorithms::catalog().iter().zip(Algorithm::ALL) {
+        assert_eq!(info.algorithm, algorithm);
+        assert_eq!(algorithm.info(), info);
+        assert_eq!(info.name.parse(), Ok(algorithm));
+        assert!(info.space != "O(1)" || info.in_place);
+    }
+
+    let info = Algorithm::InsertionSort.info();
+    assert_eq!(info.name, "Insertion Sort");
+    assert_eq!((info.best, info.average, info.worst, info.space), ("O(n)", "O(n^2)", "O(n^2)", "O(1)"));
+    assert!(info.stable && info.in_place && info.adaptive && info.deterministic);
+
+    assert!(!Algorithm::Bogosort.info().deterministic);
+    assert_eq!("Pattern-Defeating Quicksort".parse(), Ok(Algorithm::Pdqsort));
+
+    // The stable sorts keep equal elements in order.
+    let arr: Vec<(u8, usize)> = [3, 1, 2, 1, 3, 2, 1, 3].iter().copied().zip(0..).collect();
+    let mut expected = arr.clone();
+    expected.sort_by_key(|x| x.0);
+
+    // Bogobogosort would take billions of shuffles on this many elements.
+    for algorithm in Algorithm::ALL.iter().filter(|algorithm| algorithm.is_comparison_sort() && **algorithm != Algorithm::Bogobogosort) {
+        let mut arr = arr.clone();
+        algorithm.sorter::<(u8, usize)>().unwrap().sort(&mut arr);
+        if algorithm.info().stable {
+            assert_eq!(arr, expected, "{}", algorithm);
+        }
+    }
+}
+
+#[test]
+fn traced() {
+    let arr = vec![5, -3, 8, 0, -3, 2];
+    let sorted = vec![-3, -3, 0, 2, 5, 8];
+
+    // Bogobogosort would take billions of shuffles on this many elements.
+    for algorithm in Algorithm::ALL.into_iter().filter(|&algorithm| algorithm != Algorithm::Bogobogosort) {
+        let mut arr_0 = arr.clone();
+        let trace = algorithm.numeric_sorter().sort_traced(&mut arr_0);
+        assert_eq!(arr_0, sorted, "{}", algorithm);
+        assert_eq!(algorithms::replay(&arr, &trace).last(), Some(&sorted), "{}", algorithm);
+    }
+
+    let trace = [2, 1].insertion_sort_traced();
+    assert_eq!(trace, [SortEvent::Compare(1, 0), SortEvent::Swap(1, 0)]);
+
+    // The keyed sorts trace the values the keys belong to.
+    let (arr_1, trace) = algorithms::merge_sort_by_cached_key_traced(vec!["bb", "a"], |s| s.len());
+    assert_eq!(arr_1, ["a", "bb"]);
+    assert!(trace.contains(&SortEvent::Write(0, "a")));
+    assert_eq!(algorithms::replay(&["bb", "a"], &trace), [["bb", "a"], ["a", "a"], ["a", "bb"]]);
+
+    let trace = [3, 1, 2].selection_sort_traced();
+    assert!(trace.contains(&SortEvent::MarkSorted(0)));
+}
+
+#[test]
+fn observed() {
+    let arr = vec![5, -3, 8, 0, -3, 2, 7, -1];
+    let sorted = vec![-3, -3, -1, 0, 2, 5, 7, 8];
+
+    // Bogobogosort would take billions of shuffles on this many elements.
+    for algorithm in Algorithm::ALL.into_iter().filter(|&algorithm| algorithm != Algorithm::Bogobogosort) {
+        let sorter = algorithm.numeric_sorter();
+
+        // Observing every event sees the same ones a trace records.
+        let mut events = vec![];
+        let mut arr_0 = arr.clone();
+        let result = sorter.sort_observed(&mut arr_0, &mut |event: SortEvent<&i32>| {
+            events.push(event.map(|&value| value));
+            return ControlFlow::Continue(());
+        });
+        assert_eq!(result, ControlFlow::Continue(()), "{}", algorithm);
+        assert_eq!(arr_0, sorted, "{}", algorithm);
+        if algorithm.info().deterministic {
+            assert_eq!(events, sorter.sort_traced(&mut arr.clone()), "{}", algorithm);
+        }
+
+        // Stopping at the first swap leaves every element in the slice, and the observer is not
+        // notified again.
+        let mut count = 0;
+        let mut stopped = false;
+        let mut arr_1 = arr.clone();
+        let result = sorter.sort_observed(&mut arr_1, &mut |event: SortEvent<&i32>| {
+            assert!(!stopped, "{}", algorithm);
+            count += 1;
+            stopped = matches!(event, SortEvent::Swap(..) | SortEvent::Write(..));
+            return if stopped { ControlFlow::Break(()) } else { ControlFlow::Continue(()) };
+        });
+        assert_eq!(result, ControlFlow::Break(()), "{}", algorithm);
+        assert!(count < events.len(), "{}", algorithm);
+        arr_1.sort();
+        assert_eq!(arr_1, sorted, "{}", algorithm);
+    }
+
+    // Nothing is swapped once the sort is stopped at a comparison.
+    let mut arr_2 = [2, 1];
+    let result = arr_2.insertion_sort_observed(|_: SortEvent<&i32>| ControlFlow::Break(()));
+    assert_eq!(result, ControlFlow::Break(()));
+    assert_eq!(arr_2, [2, 1]);
+
+    // Even the slowest sorts stop right away.
+    let mut comparisons = 0;
+    let (_, result) = algorithms::slowsort_observed((0..1000).rev().collect(), |event: SortEvent<&i32>| {
+        comparisons += matches!(event, SortEvent::Compare(..)) as usize;
+        return if comparisons < 100 { ControlFlow::Continue(()) } else { ControlFlow::Break(()) };
+    });
+    assert_eq!(result, ControlFlow::Break(()));
+    assert_eq!(comparisons, 100);
+}
+
+#[test]
+fn counted() {
+    let arr = vec![5, -3, 8, 0, -3, 2, 7, -1];
+
+    // The counts are those of the events a trace records.
+    for algorithm in Algorithm::ALL {
+        if !algorithm.info().deterministic {
+            continue;
+        }
+
+        let sorter = algorithm.numeric_sorter();
+        let trace = sorter.sort_traced(&mut arr.clone());
+        let count = |f: fn(&SortEvent<i32>) -> bool| trace.iter().filter(|&event| f(event)).count();
+
+        let stats = sorter.sort_counted(&mut arr.clone());
+        assert_eq!(stats.comparisons, count(|event| matches!(event, SortEvent::Compare(..))), "{}", algorithm);
+        assert_eq!(stats.swaps, count(|event| matches!(event, SortEvent::Swap(..))), "{}", algorithm);
+        assert_eq!(stats.reads, count(|event| matches!(event, SortEvent::Read(..))), "{}", algorithm);
+        assert_eq!(stats.writes, count(|event| matches!(event, SortEvent::Write(..))), "{}", algorithm);
+    }
+
+    // Insertion sort does n-1 comparisons and nothing else on sorted input.
+    let stats = (0..100).collect::<Vec<i32>>().insertion_sort_counted();
+    assert_eq!(stats, SortStats { comparisons: 99, ..SortStats::default() });
+
+    let mut calls = 0;
+    let stats = arr.clone().timsort_by_counted(|a, b| {
+        calls += 1;
+        return a.cmp(b);
+    });
+    assert_eq!(stats.comparisons, calls);
+
+    // Merge sort halves the array on every level and allocates the halves and the merged ones.
+    let (_, stats) = algorithms::merge_sort_counted(arr.clone());
+    assert_eq!(stats.recursion_depth, 2);
+    assert!(stats.allocations > 0);
+
+    let stats = arr.clone().heap_sort_counted();
+    assert_eq!((stats.recursion_depth, stats.allocations), (0, 0));
+}
+
+#[test]
+fn steps() {
+    let arr = vec![5, -3, 8, 0, -3, 2];
+    let sorted = vec![-3, -3, 0, 2, 5, 8];
+
+    // The steps are those a trace replays to.
+    let trace = arr.clone().merge_sort_traced();
+    let steps: Vec<algorithms::Step<i32>> = algorithms::merge_sort_steps(arr.clone()).collect();
+    assert_eq!(steps.iter().map(|step| step.event).collect::<Vec<_>>(), trace);
+    let changes = steps.iter().filter(|step| matches!(step.event, SortEvent::Swap(..) | SortEvent::Write(..)));
+    assert_eq!(changes.map(|step| step.arr.clone()).collect::<Vec<_>>(), algorithms::replay(&arr, &trace)[1..]);
+    assert_eq!(steps.last().unwrap().arr, sorted);
+
+    assert_eq!(algorithms::quicksort_by_key_steps(arr.clone(), |&x: &i32| -x).last().unwrap().arr, [8, 5, 2, 0, -3, -3]);
+    assert_eq!(algorithms::tree_sort_steps(arr.clone()).last().unwrap().arr, sorted);
+    assert_eq!(algorithms::lsd_radix_sort_steps(arr.clone()).last().unwrap().arr, sorted);
+
+    let mut steps = algorithms::insertion_sort_steps(vec![2, 1]);
+    assert_eq!(steps.next(), Some(algorithms::Step { event: SortEvent::Compare(1, 0), arr: vec![2, 1] }));
+    assert_eq!(steps.next(), Some(algorithms::Step { event: SortEvent::Swap(1, 0), arr: vec![1, 2] }));
+    assert_eq!(steps.next(), None);
+    assert_eq!(steps.next(), None);
+
+    // Only as much of the sort runs as is taken, which for slowsort on 1000 elements is very
+    // little, and dropping the rest cancels it.
+    let steps = algorithms::slowsort_steps((0..1000).rev().collect::<Vec<i32>>());
+    assert_eq!(steps.take(100).count(), 100);
+
+    assert_eq!(algorithms::bubble_sort_with_nans_steps(vec![1.0, f64::NAN], NanPolicy::Error).err(), Some(NanError { index: 1 }));
+}
+
+#[test]
+fn steps_dropped() {
+    let comparisons = Arc::new(AtomicUsize::new(0));
+    let counting = |comparisons: &Arc<AtomicUsize>| {
+        let comparisons = Arc::clone(comparisons);
+        return move |a: &i32, b: &i32| {
+            comparisons.fetch_add(1, Ordering::SeqCst);
+            return a.cmp(b);
+        };
+    };
+
+    // Dropping the iterator waits for the sort to stop, so by then the thread has let go of the
+    // comparator, having compared barely more than the steps taken.
+    let mut steps = algorithms::slowsort_by_steps((0..1000).rev().collect(), counting(&comparisons));
+    assert_eq!(steps.by_ref().take(10).count(), 10);
+    drop(steps);
+    assert_eq!(Arc::strong_count(&comparisons), 1);
+    assert!(comparisons.load(Ordering::SeqCst) <= 11);
+
+    // The same goes for an iterator which is never advanced, whose sort is stuck at its first event.
+    comparisons.store(0, Ordering::SeqCst);
+    drop(algorithms::slowsort_by_steps((0..1000).rev().collect(), counting(&comparisons)));
+    assert_eq!(Arc::strong_count(&comparisons), 1);
+    assert!(comparisons.load(Ordering::SeqCst) <= 1);
+
+    // And for one dropped on another thread than the one it was made on.
+    let steps = algorithms::slowsort_by_steps((0..1000).rev().collect(), counting(&comparisons));
+    std::thread::spawn(move || drop(steps)).join().unwrap();
+    assert_eq!(Arc::strong_count(&comparisons), 1);
+}
+
+#[test]
+fn limited() {
+    let arr = vec![5, -3, 8, 0, -3, 2, 7, -1];
+    let sorted = vec![-3, -3, -1, 0, 2, 5, 7, 8];
+    let limits = SortLimits { max_comparisons: Some(5), ..SortLimits::default() };
+
+    // Bogobogosort would take billions of shuffles on this many elements.
+    for algorithm in Algorithm::ALL.into_iter().filter(|&algorithm| algorithm != Algorithm::Bogobogosort) {
+        let sorter = algorithm.numeric_sorter();
+
+        let mut arr_0 = arr.clone();
+        assert_eq!(sorter.sort_limited(&mut arr_0, SortLimits::default()), Ok(()), "{}", algorithm);
+        assert_eq!(arr_0, sorted, "{}", algorithm);
+
+        // The non-comparison sorts make no comparisons to limit.
+        let mut arr_1 = arr.clone();
+        match sorter.sort_limited(&mut arr_1, limits) {
+            Ok(()) => assert!(!algorithm.is_comparison_sort(), "{}", algorithm),
+            Err(aborted) => {
+                assert_eq!(aborted.reason, AbortReason::MaxComparisons, "{}", algorithm);
+                assert_eq!(aborted.stats.comparisons, 5, "{}", algorithm);
+                assert_eq!(aborted.arr, arr_1, "{}", algorithm);
+
+                // The slice is left partially sorted, but holds all of its elements.
+                arr_1.sort();
+                assert_eq!(arr_1, sorted, "{}", algorithm);
+            },
+        }
+    }
+
+    let limits = SortLimits { max_steps: Some(1000), ..SortLimits::default() };
+    let aborted = algorithms::bogosort_limited((0..20).rev().collect::<Vec<i32>>(), limits).unwrap_err();
+    assert_eq!(aborted.reason, AbortReason::MaxSteps);
+    assert_eq!(aborted.to_string(), "the sort was aborted because it exceeded its maximum number of steps");
+
+    let limits = SortLimits { deadline: Some(Instant::now() + Duration::from_millis(10)), ..SortLimits::default() };
+    let aborted = algorithms::slowsort_limited((0..1000).rev().collect::<Vec<i32>>(), limits).unwrap_err();
+    assert_eq!(aborted.reason, AbortReason::Deadline);
+    assert!(aborted.stats.comparisons > 0);
+
+    assert_eq!(algorithms::stooge_sort_limited(vec![2, 1], SortLimits::default()), Ok(vec![1, 2]));
+    assert_eq!([1.0, f64::NAN].insertion_sort_with_nans_limited(NanPolicy::Error, limits), Err(NanError { index: 1 }));
+}
+
+#[test]
+fn seeded() {
+    let arr = vec![5, -3, 8, 0, -3, 2];
+    let sorted = vec![-3, -3, 0, 2, 5, 8];
+
+    // The same seed shuffles the same way on every run.
+    let (arr_0, trace_0) = algorithms::bogosort_with_seed_traced(arr.clone(), 42);
+    let (arr_1, trace_1) = algorithms::bogosort_with_seed_traced(arr.clone(), 42);
+    assert_eq!(arr_0, sorted);
+    assert_eq!((arr_1, &trace_1), (sorted.clone(), &trace_0));
+
+    let mut arr_2 = arr.clone();
+    assert_eq!(arr_2.bogosort_with_rng_traced(&mut StdRng::seed_from_u64(42)), trace_0);
+    assert_eq!(arr_2, sorted);
+
+    let steps: Vec<SortEvent<i32>> = algorithms::bogosort_with_seed_steps(arr.clone(), 42).map(|step| step.event).collect();
+    assert_eq!(steps, trace_0);
+
+    let arr_3: Vec<i32> = (0..100).rev().collect();
+    let (arr_4, trace_2) = algorithms::quicksort_with_seed_traced(arr_3.clone(), 7);
+    assert_eq!(arr_4, (0..100).collect::<Vec<i32>>());
+    assert_eq!(arr_3.clone().quicksort_with_seed_traced(7), trace_2);
+    assert_eq!(arr_3.clone().quicksort_with_seed_counted(7), arr_3.clone().quicksort_with_rng_counted(&mut StdRng::seed_from_u64(7)));
+}
+
+#[test]
+fn expected_iterations() {
+    assert_eq!(algorithms::bogosort_expected_iterations(4), 23.0);
+    assert_eq!(algorithms::bogobogosort_expected_iterations(3), 71.0);
+    assert_eq!(algorithms::bogobogosort_expected_iterations(4), 6911.0);
+    assert_eq!(algorithms::permutation_sort_expected_iterations(4), 11.5);
+    assert_eq!(algorithms::bogosort_expected_iterations(171), f64::INFINITY);
+
+    for (n, expected) in [(0, 0.0), (1, 0.0), (2, 1.0), (3, 6.75), (4, 33.0)] {
+        assert!((algorithms::bozosort_expected_iterations(n) - expected).abs() < 1e-9, "{}", n);
+    }
+    assert!(algorithms::bozosort_expected_iterations(20) > algorithms::bogosort_expected_iterations(20));
+
+    // The series taking over from the partitions above 40 elements carries on where they left off.
+    let ratio = algorithms::bozosort_expected_iterations(41) / algorithms::bozosort_expected_iterations(40);
+    assert!((ratio / 41.0 - 1.0).abs() < 1e-3);
+    assert!(algorithms::bozosort_expected_iterations(170).is_finite());
+
+    // Starting from every permutation of 4 distinct elements but the sorted one, in the order
+    // permutation sort steps through them, it takes 23 steps down to 1.
+    let mut total = 0;
+    let mut arr = vec![0, 1, 3, 2];
+    for _ in 0..23 {
+        let steps = arr.clone().permutation_sort_stepped();
+        total += steps.len() - 1;
+        arr = steps[1].clone();
+    }
+    assert_eq!(total as f64 / 24.0, Algorithm::PermutationSort.expected_iterations(4).unwrap());
+    assert_eq!(Algorithm::BubbleSort.expected_iterations(4), None);
+}
+
+#[test]
+fn render() {
+    let arr = vec![2, 1];
+    let (steps, highlights) = render::highlighted_steps(&arr, &arr.clone().insertion_sort_traced());
+    assert_eq!(steps, [[2, 1], [2, 1], [1, 2]]);
+    assert_eq!(highlights, [vec![], vec![Highlight::Compared(1), Highlight::Compared(0)], vec![Highlight::Changed(1), Highlight::Changed(0)]]);
+
+    let options = RenderOptions { width: 40, height: 10, frame_rate: 2, changed: Color::rgb(0xff, 0, 0), ..RenderOptions::default() };
+
+    // Both bars change their height and color, and the higher one takes up the whole height.
+    let mut svg = vec![];
+    render::write_svg(&mut svg, &steps, Some(&highlights), &options).unwrap();
+    let svg = String::from_utf8(svg).unwrap();
+    assert!(svg.starts_with("<svg") && svg.trim_end().ends_with("</svg>"));
+    assert_eq!(svg.matches("<rect x=").count(), 2);
+    assert!(svg.contains(r#"<animate attributeName="height" values="10;10;5" dur="1.5s""#));
+    assert!(svg.contains("#ff0000"));
+
+    let mut gif = vec![];
+    render::write_gif(&mut gif, &steps, Some(&highlights), &options).unwrap();
+    assert!(gif.starts_with(b"GIF89a\x28\x00\x0a\x00"));
+    assert!(gif.windows(11).any(|window| window == b"NETSCAPE2.0"));
+    assert_eq!(gif.last(), Some(&0x3b));
+
+    // The stepped variants have no highlights, and any elements with an order can be drawn.
+    let (_, steps) = algorithms::merge_sort_stepped(vec!["pear", "fig", "apple"]);
+    let mut svg = vec![];
+    render::write_svg(&mut svg, &steps, None, &RenderOptions::default()).unwrap();
+    assert!(!String::from_utf8(svg).unwrap().contains(r#"attributeName="fill""#));
+}
+
+#[test]
+fn render_html() {
+    let arr = vec![2, 1];
+    let trace = arr.clone().insertion_sort_traced();
+
+    // The elements are ranked, and every event is its kind and its indices.
+    let mut html = vec![];
+    render::write_html(&mut html, &arr, &[("insertion_sort", &trace)], &RenderOptions::default()).unwrap();
+    let html = String::from_utf8(html).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>insertion_sort</title>"));
+    assert!(html.contains(r#""initial":[1,0],"traces":[{"name":"insertion_sort","events":[0,1,0,1,1,0]}]"#));
+
+    // Names cannot end the script they are embedded in, and several sorts are played side by side.
+    let arr = vec![5, 2, 4, 1, 3];
+    let (_, gnome) = algorithms::gnome_sort_traced(arr.clone());
+    let (_, insertion) = algorithms::insertion_sort_traced(arr.clone());
+    let mut html = vec![];
+    render::write_html(&mut html, &arr, &[("gnome</script>", &gnome), ("insertion_sort", &insertion)], &RenderOptions::default()).unwrap();
+    let html = String::from_utf8(html).unwrap();
+    assert!(html.contains("<title>gnome&lt;/script&gt; vs. insertion_sort</title>"));
+    assert!(html.contains(r#"{"name":"gnome\u003c/script>","events":["#));
+    assert_eq!(html.matches("</script>").count(), 2);
+
+    // Placeholders in the names are left as they are rather than filled in.
+    let mut html = vec![];
+    render::write_html(&mut html, &arr, &[("{{data}}", &gnome), ("{{title}}", &insertion)], &RenderOptions::default()).unwrap();
+    let html = String::from_utf8(html).unwrap();
+    assert!(html.contains("<title>{{data}} vs. {{title}}</title>"));
+    assert!(html.contains(r#"{"name":"{{data}}","events":["#));
+    assert!(html.contains(r#"{"name":"{{title}}","events":["#));
+    assert_eq!(html.matches(r#""initial":"#).count(), 1);
+}
+
+#[test]
+fn render_nans() {
+    // `NaN`s are ranked last rather than breaking the order the bars are ranked by.
+    let nan = f64::NAN;
+    let arr: Vec<f64> = (0..64).map(|i| if i % 3 == 0 { nan } else { (i * 37 % 64) as f64 }).collect();
+    let (_, steps) = algorithms::insertion_sort_stepped(arr.clone());
+    let (_, trace) = algorithms::insertion_sort_traced(arr.clone());
+    let (highlighted, highlights) = render::highlighted_steps(&arr, &trace);
+
+    let mut svg = vec![];
+    render::write_svg(&mut svg, &steps, None, &RenderOptions::default()).unwrap();
+    assert!(!svg.is_empty());
+
+    let mut gif = vec![];
+    render::write_gif(&mut gif, &highlighted, Some(&highlights), &RenderOptions::default()).unwrap();
+    assert!(gif.starts_with(b"GIF89a"));
+
+    let arr = vec![3.0, nan, 1.0, 2.0, nan, 0.5];
+    let (_, trace) = algorithms::insertion_sort_traced(arr.clone());
+    let mut html = vec![];
+    render::write_html(&mut html, &arr, &[("insertion_sort", &trace)], &RenderOptions::default()).unwrap();
+    let html = String::from_utf8(html).unwrap();
+    assert!(html.contains(r#""initial":[3,4,1,2,4,0]"#));
+}
+
+#[test]
+#[should_panic]
+fn render_with_missing_highlights() {
+    let (_, steps) = algorithms::bubble_sort_stepped(vec![2, 1]);
+    render::write_gif(vec![], &steps, Some(&[]), &RenderOptions::default()).unwrap();
 }